@@ -0,0 +1,83 @@
+use glfw::{Context, Glfw, Window, WindowEvent};
+use imgui::{Context as ImContext, SuspendedContext};
+use imgui_glfw_rs::glfw;
+use imgui_glfw_rs::imgui;
+use imgui_glfw_rs::ImguiGLFW;
+use std::sync::mpsc::Receiver;
+
+struct ImguiWindow {
+    window: Window,
+    events: Receiver<(f64, WindowEvent)>,
+    imgui: Option<SuspendedContext>,
+    imgui_glfw: ImguiGLFW,
+}
+
+impl ImguiWindow {
+    fn new(glfw: &mut Glfw, title: &str) -> Self {
+        let (mut window, events) = glfw
+            .create_window(640, 480, title, glfw::WindowMode::Windowed)
+            .expect("Failed to create window");
+
+        window.make_current();
+        window.set_all_polling(true);
+
+        gl::load_with(|symbol| window.get_proc_address(symbol) as *const _);
+        unsafe {
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            gl::ClearColor(0.1, 0.1, 0.1, 1.0);
+        }
+
+        // Only one imgui context can be active at a time.
+        let mut imgui = ImContext::create();
+        let imgui_glfw = ImguiGLFW::new(&mut imgui, &mut window);
+
+        Self {
+            window,
+            events,
+            imgui: Some(imgui.suspend()),
+            imgui_glfw,
+        }
+    }
+
+    fn frame(&mut self) {
+        self.window.make_current();
+        let mut imgui = match self.imgui.take().unwrap().activate() {
+            Ok(imgui) => imgui,
+            Err(_) => panic!("Another imgui context is still active"),
+        };
+
+        unsafe {
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+
+        let ui = self.imgui_glfw.frame(&mut self.window, &mut imgui);
+        ui.show_demo_window(&mut true);
+        self.imgui_glfw.draw(ui, &mut self.window);
+
+        self.window.swap_buffers();
+
+        for (_, event) in glfw::flush_messages(&self.events) {
+            self.imgui_glfw.handle_event(&mut imgui, &event);
+        }
+
+        self.imgui = Some(imgui.suspend());
+    }
+}
+
+fn main() {
+    let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).unwrap();
+    glfw.window_hint(glfw::WindowHint::ContextVersion(3, 3));
+
+    let mut windows = [
+        ImguiWindow::new(&mut glfw, "imgui-glfw-rs window 1"),
+        ImguiWindow::new(&mut glfw, "imgui-glfw-rs window 2"),
+    ];
+
+    while windows.iter().any(|w| !w.window.should_close()) {
+        glfw.poll_events();
+        for window in windows.iter_mut().filter(|w| !w.window.should_close()) {
+            window.frame();
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! # Example use
 //! You can run this example with `cargo run --example hello_world`
 //!
-//! ```rust
+//! ```rust,no_run
 //! use glfw::Context;
 //! use imgui::Context as ImContext;
 //! use imgui_glfw_rs::glfw;
@@ -65,20 +65,85 @@ pub use glfw;
 /// Use the reexported imgui crate to avoid version conflicts.
 pub use imgui;
 
+#[cfg(feature = "raw-window-handle")]
+mod raw_handle;
+#[cfg(feature = "raw-window-handle")]
+pub use raw_handle::raw_handles;
+
 use glfw::ffi::GLFWwindow;
-use glfw::{Action, Key, Modifiers, MouseButton, StandardCursor, Window, WindowEvent};
-use imgui::{ConfigFlags, Context, Key as ImGuiKey, MouseCursor, Ui};
+use glfw::Context as _;
+use glfw::{
+    Action, JoystickEvent, JoystickId, Key, Modifiers, MouseButton, StandardCursor, Window,
+    WindowEvent,
+};
+use imgui::{BackendFlags, ConfigFlags, Context, DrawData, Key as ImGuiKey, MouseCursor, Ui};
+#[cfg(feature = "opengl")]
+use imgui::{FontConfig, FontGlyphRanges, FontId, FontSource, TextureId};
+#[cfg(feature = "opengl")]
 use imgui_opengl_renderer::Renderer;
 use std::ffi::CStr;
-use std::os::raw::c_void;
-use std::time::Instant;
+use std::os::raw::{c_int, c_void};
+use std::path::PathBuf;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+const FIRST_FRAME_DELTA: f32 = 1e-6;
+/// Largest scroll distance per event, a wheel notch is `1.0`.
+const MAX_SCROLL: f32 = 100.;
+
+const KEY_MAPPING: [(ImGuiKey, Key); 21] = [
+    (ImGuiKey::Tab, Key::Tab),
+    (ImGuiKey::LeftArrow, Key::Left),
+    (ImGuiKey::RightArrow, Key::Right),
+    (ImGuiKey::UpArrow, Key::Up),
+    (ImGuiKey::DownArrow, Key::Down),
+    (ImGuiKey::PageUp, Key::PageUp),
+    (ImGuiKey::PageDown, Key::PageDown),
+    (ImGuiKey::Home, Key::Home),
+    (ImGuiKey::End, Key::End),
+    (ImGuiKey::Insert, Key::Insert),
+    (ImGuiKey::Delete, Key::Delete),
+    (ImGuiKey::Backspace, Key::Backspace),
+    (ImGuiKey::Space, Key::Space),
+    (ImGuiKey::Enter, Key::Enter),
+    (ImGuiKey::Escape, Key::Escape),
+    (ImGuiKey::A, Key::A),
+    (ImGuiKey::C, Key::C),
+    (ImGuiKey::V, Key::V),
+    (ImGuiKey::X, Key::X),
+    (ImGuiKey::Y, Key::Y),
+    (ImGuiKey::Z, Key::Z),
+];
+
+/// Translate a glfw key into the imgui key the backend maps it to.
+pub fn to_imgui_key(key: Key) -> Option<ImGuiKey> {
+    KEY_MAPPING
+        .iter()
+        .find(|&&(_, glfw_key)| glfw_key == key)
+        .map(|&(imgui_key, _)| imgui_key)
+}
+
+/// Clipboard contents above this size in bytes are logged when pasted.
+#[cfg(feature = "log")]
+const LARGE_CLIPBOARD_SIZE: usize = 1024 * 1024;
 
 struct GlfwClipboardBackend(*mut c_void);
 
 impl imgui::ClipboardBackend for GlfwClipboardBackend {
+    // imgui only reads the clipboard when the user pastes into a text field.
     fn get(&mut self) -> Option<imgui::ImString> {
         let char_ptr = unsafe { glfw::ffi::glfwGetClipboardString(self.0 as *mut GLFWwindow) };
+        if char_ptr.is_null() {
+            return None;
+        }
         let c_str = unsafe { CStr::from_ptr(char_ptr) };
+        #[cfg(feature = "log")]
+        {
+            let size = c_str.to_bytes().len();
+            if size > LARGE_CLIPBOARD_SIZE {
+                log::warn!("imgui-glfw-rs: pasting {} bytes from the clipboard", size);
+            }
+        }
         Some(imgui::ImString::new(c_str.to_str().unwrap()))
     }
 
@@ -89,144 +154,2236 @@ impl imgui::ClipboardBackend for GlfwClipboardBackend {
     }
 }
 
-pub struct ImguiGLFW {
-    last_frame: Instant,
-    mouse_press: [bool; 5],
-    cursor_pos: (f64, f64),
-    cursor: (MouseCursor, Option<StandardCursor>),
+/// The cursor calls made on the window, tests record them instead.
+trait CursorWindow {
+    fn set_cursor_mode(&mut self, mode: glfw::CursorMode);
+    fn set_cursor(&mut self, cursor: glfw::Cursor);
+}
+
+impl CursorWindow for Window {
+    fn set_cursor_mode(&mut self, mode: glfw::CursorMode) {
+        Window::set_cursor_mode(self, mode);
+    }
 
-    renderer: Renderer,
+    fn set_cursor(&mut self, cursor: glfw::Cursor) {
+        Window::set_cursor(self, Some(cursor));
+    }
 }
 
-impl ImguiGLFW {
-    pub fn new(imgui: &mut Context, window: &mut Window) -> Self {
-        unsafe {
-            let window_ptr = glfw::ffi::glfwGetCurrentContext() as *mut c_void;
-            imgui.set_clipboard_backend(Box::new(GlfwClipboardBackend(window_ptr)));
-        }
-
-        let mut io_mut = imgui.io_mut();
-        io_mut.key_map[ImGuiKey::Tab as usize] = Key::Tab as u32;
-        io_mut.key_map[ImGuiKey::LeftArrow as usize] = Key::Left as u32;
-        io_mut.key_map[ImGuiKey::RightArrow as usize] = Key::Right as u32;
-        io_mut.key_map[ImGuiKey::UpArrow as usize] = Key::Up as u32;
-        io_mut.key_map[ImGuiKey::DownArrow as usize] = Key::Down as u32;
-        io_mut.key_map[ImGuiKey::PageUp as usize] = Key::PageUp as u32;
-        io_mut.key_map[ImGuiKey::PageDown as usize] = Key::PageDown as u32;
-        io_mut.key_map[ImGuiKey::Home as usize] = Key::Home as u32;
-        io_mut.key_map[ImGuiKey::End as usize] = Key::End as u32;
-        io_mut.key_map[ImGuiKey::Insert as usize] = Key::Insert as u32;
-        io_mut.key_map[ImGuiKey::Delete as usize] = Key::Delete as u32;
-        io_mut.key_map[ImGuiKey::Backspace as usize] = Key::Backspace as u32;
-        io_mut.key_map[ImGuiKey::Space as usize] = Key::Space as u32;
-        io_mut.key_map[ImGuiKey::Enter as usize] = Key::Enter as u32;
-        io_mut.key_map[ImGuiKey::Escape as usize] = Key::Escape as u32;
-        io_mut.key_map[ImGuiKey::A as usize] = Key::A as u32;
-        io_mut.key_map[ImGuiKey::C as usize] = Key::C as u32;
-        io_mut.key_map[ImGuiKey::V as usize] = Key::V as u32;
-        io_mut.key_map[ImGuiKey::X as usize] = Key::X as u32;
-        io_mut.key_map[ImGuiKey::Y as usize] = Key::Y as u32;
-        io_mut.key_map[ImGuiKey::Z as usize] = Key::Z as u32;
-
-        let renderer = Renderer::new(imgui, |s| window.get_proc_address(s) as _);
+/// The windowing platform GLFW was built for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlfwPlatform {
+    Win32,
+    Cocoa,
+    X11,
+    Wayland,
+}
+
+impl GlfwPlatform {
+    // GLFW 3.3 has no runtime platform query, each build supports a single platform.
+    fn detect() -> Self {
+        if cfg!(target_os = "windows") {
+            GlfwPlatform::Win32
+        } else if cfg!(target_os = "macos") {
+            GlfwPlatform::Cocoa
+        } else if cfg!(feature = "wayland") {
+            GlfwPlatform::Wayland
+        } else {
+            GlfwPlatform::X11
+        }
+    }
+
+    /// Wayland doesn't allow applications to warp the cursor.
+    fn can_set_mouse_pos(self) -> bool {
+        self != GlfwPlatform::Wayland
+    }
+}
+
+/// imgui's built-in color styles, see `ImguiGLFW::set_theme`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Theme {
+    Dark,
+    Light,
+    Classic,
+}
+
+/// The kind of an input event imgui can capture, see `ImguiGLFW::last_captured_event`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowEventKind {
+    MouseButton,
+    CursorPos,
+    Scroll,
+    Key,
+    /// `Char` and `CharModifiers`.
+    Char,
+}
 
+impl WindowEventKind {
+    fn of(event: &WindowEvent) -> Option<Self> {
+        match *event {
+            WindowEvent::MouseButton(..) => Some(WindowEventKind::MouseButton),
+            WindowEvent::CursorPos(..) => Some(WindowEventKind::CursorPos),
+            WindowEvent::Scroll(..) => Some(WindowEventKind::Scroll),
+            WindowEvent::Key(..) => Some(WindowEventKind::Key),
+            WindowEvent::Char(..) | WindowEvent::CharModifiers(..) => Some(WindowEventKind::Char),
+            _ => None,
+        }
+    }
+}
+
+/// Statistics of a rendered frame, see `ImguiGLFW::last_frame_stats`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameStats {
+    pub draw_list_count: usize,
+    pub vertex_count: usize,
+    pub index_count: usize,
+    /// Wall-clock time spent in the renderer, zero for `prepare_render`.
+    pub render_time: Duration,
+}
+
+/// Builder for `ImguiGLFW` with non-default options.
+pub struct ImguiGLFWBuilder {
+    headless: bool,
+    clipboard: bool,
+    custom_clipboard: Option<Box<dyn imgui::ClipboardBackend>>,
+    double_click_time: Option<f32>,
+    ini_filename: Option<Option<PathBuf>>,
+    config_flags: Option<ConfigFlags>,
+    #[cfg(feature = "opengl")]
+    renderer: bool,
+    #[cfg(feature = "opengl")]
+    textures: Vec<u32>,
+}
+
+impl Default for ImguiGLFWBuilder {
+    fn default() -> Self {
         Self {
-            last_frame: Instant::now(),
+            headless: false,
+            clipboard: true,
+            custom_clipboard: None,
+            double_click_time: None,
+            ini_filename: None,
+            config_flags: None,
+            #[cfg(feature = "opengl")]
+            renderer: true,
+            #[cfg(feature = "opengl")]
+            textures: Vec::new(),
+        }
+    }
+}
+
+impl ImguiGLFWBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never touch the OS cursor, e.g. for offscreen rendering in tests.
+    pub fn with_headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Install the glfw clipboard backend (default). Disable it in sandboxes
+    /// without clipboard access, imgui then keeps its own no-op clipboard.
+    pub fn with_clipboard(mut self, clipboard: bool) -> Self {
+        self.clipboard = clipboard;
+        self
+    }
+
+    /// Install `backend` instead of the glfw clipboard, e.g. to route the clipboard through a
+    /// remote session or an in-memory buffer in tests. imgui owns the backend from then on,
+    /// so anything it refers to must live as long as the imgui context.
+    pub fn with_custom_clipboard(mut self, backend: Box<dyn imgui::ClipboardBackend>) -> Self {
+        self.custom_clipboard = Some(backend);
+        self
+    }
+
+    /// Maximum time in seconds between two clicks to count as a double click.
+    pub fn with_double_click_time(mut self, seconds: f32) -> Self {
+        self.double_click_time = Some(seconds);
+        self
+    }
+
+    /// Where imgui stores its window state, `None` disables persistence.
+    pub fn with_ini_filename(mut self, ini_filename: Option<PathBuf>) -> Self {
+        self.ini_filename = Some(ini_filename);
+        self
+    }
+
+    /// Replace imgui's `io.config_flags`, e.g. `NAV_ENABLE_KEYBOARD | NO_MOUSE_CURSOR_CHANGE`.
+    /// With `NO_MOUSE_CURSOR_CHANGE` the backend doesn't advertise cursor support to imgui.
+    pub fn with_config_flags(mut self, config_flags: ConfigFlags) -> Self {
+        self.config_flags = Some(config_flags);
+        self
+    }
+
+    /// Create the bundled OpenGL renderer (default). Disable it when you render imgui
+    /// yourself and only want the input, clipboard and cursor handling.
+    #[cfg(feature = "opengl")]
+    pub fn with_renderer(mut self, renderer: bool) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// OpenGL textures to register at construction, their `TextureId`s are returned by
+    /// `ImguiGLFW::textures` in the same order.
+    #[cfg(feature = "opengl")]
+    pub fn with_textures(mut self, gl_texture_ids: Vec<u32>) -> Self {
+        self.textures = gl_texture_ids;
+        self
+    }
+
+    /// Set up imgui for `window` and create the renderer.
+    ///
+    /// This sets `io.font_global_scale` from the window's content scale. For crisp text,
+    /// build your fonts at the scaled size afterwards and reset the scale to `1.0`.
+    pub fn build(self, imgui: &mut Context, window: &mut Window) -> ImguiGLFW {
+        self.build_for(imgui, Some(window))
+    }
+
+    /// The window is only left out in tests, there is no display to open one on.
+    fn build_for(self, imgui: &mut Context, window: Option<&mut Window>) -> ImguiGLFW {
+        if let Some(ini_filename) = self.ini_filename {
+            // imgui stores the path as a C string.
+            imgui.set_ini_filename(
+                ini_filename.map(|path| imgui::ImString::new(path.to_string_lossy())),
+            );
+        }
+
+        let window_ptr = window
+            .as_ref()
+            .map_or(ptr::null_mut(), |window| window.window_ptr());
+        if let Some(backend) = self.custom_clipboard {
+            imgui.set_clipboard_backend(backend);
+        } else if self.clipboard && !window_ptr.is_null() {
+            imgui.set_clipboard_backend(Box::new(GlfwClipboardBackend(window_ptr as *mut c_void)));
+        } else if self.clipboard {
+            #[cfg(feature = "log")]
+            log::warn!("imgui-glfw-rs: glfw window pointer is null, clipboard is disabled");
+        }
+
+        let platform = GlfwPlatform::detect();
+
+        let io_mut = imgui.io_mut();
+        if let Some(config_flags) = self.config_flags {
+            io_mut.config_flags = config_flags;
+        }
+        if !self.headless
+            && !io_mut
+                .config_flags
+                .contains(ConfigFlags::NO_MOUSE_CURSOR_CHANGE)
+        {
+            io_mut.backend_flags.insert(BackendFlags::HAS_MOUSE_CURSORS);
+        }
+        if !self.headless && platform.can_set_mouse_pos() {
+            io_mut.backend_flags.insert(BackendFlags::HAS_SET_MOUSE_POS);
+        }
+        if let Some(double_click_time) = self.double_click_time {
+            io_mut.mouse_double_click_time = double_click_time;
+        }
+        for &(imgui_key, glfw_key) in KEY_MAPPING.iter() {
+            io_mut.key_map[imgui_key as usize] = glfw_key as u32;
+        }
+
+        // Gamepads connected before startup don't send a joystick event.
+        let gamepads: Vec<JoystickId> = window.as_ref().map_or_else(Vec::new, |window| {
+            (0..=glfw::ffi::JOYSTICK_LAST)
+                .filter_map(JoystickId::from_i32)
+                .filter(|&joystick| window.glfw.get_joystick(joystick).is_gamepad())
+                .collect()
+        });
+        io_mut
+            .backend_flags
+            .set(BackendFlags::HAS_GAMEPAD, !gamepads.is_empty());
+
+        // Start with the right scale instead of waiting for the first frame. Where the
+        // framebuffer isn't scaled up (e.g. Windows), the content scale enlarges the text.
+        let window_size = window.as_ref().map_or((0, 0), |window| window.get_size());
+        let framebuffer_size = window
+            .as_ref()
+            .map_or((0, 0), |window| window.get_framebuffer_size());
+        if window_size.0 > 0 && window_size.1 > 0 {
+            io_mut.display_framebuffer_scale = [
+                framebuffer_size.0 as f32 / window_size.0 as f32,
+                framebuffer_size.1 as f32 / window_size.1 as f32,
+            ];
+        }
+        let content_scale = window
+            .as_ref()
+            .map_or(1., |window| window.get_content_scale().0);
+        let font_scale = content_scale / io_mut.display_framebuffer_scale[0];
+        let font_scale = if font_scale.is_finite() && font_scale > 0. {
+            font_scale
+        } else {
+            1.
+        };
+        io_mut.font_global_scale = font_scale;
+
+        // `get_proc_address` makes the window's context current, input-only windows may not
+        // have a GL context at all (`ClientApi::NoApi`).
+        #[cfg(feature = "opengl")]
+        let mut window = window;
+        #[cfg(feature = "opengl")]
+        let (renderer, gl_version) = match window.as_mut() {
+            Some(window) if self.renderer => {
+                let renderer = Renderer::new(imgui, |s| window.get_proc_address(s) as _);
+                gl::load_with(|s| window.get_proc_address(s) as _);
+                let version = window.get_context_version();
+                (Some(renderer), (version.major as u32, version.minor as u32))
+            }
+            _ => (None, (0, 0)),
+        };
+        #[cfg(feature = "opengl")]
+        let font_texture_id = imgui.fonts().tex_id;
+        // Same as `register_texture`, the renderer binds texture ids as GL texture names.
+        #[cfg(feature = "opengl")]
+        let textures = self
+            .textures
+            .iter()
+            .map(|&gl_texture_id| TextureId::from(gl_texture_id as usize))
+            .collect();
+        // The renderer binds its own vertex array object, which needs OpenGL 3.0.
+        #[cfg(all(feature = "opengl", feature = "log"))]
+        {
+            if renderer.is_some() && gl_version < (3, 0) {
+                log::warn!(
+                    "imgui-glfw-rs: OpenGL {}.{} has no vertex array objects, imgui won't render",
+                    gl_version.0,
+                    gl_version.1
+                );
+            }
+        }
+
+        ImguiGLFW {
+            window_ptr,
+            platform,
+            last_frame: None,
+            frame_active: false,
+            delta_time: 0.,
+            max_delta: 0.1,
             mouse_press: [false; 5],
+            mouse_just_pressed: [false; 5],
+            mouse_changed: [None; 5],
+            button_mapping: [
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                None,
+                None,
+                None,
+            ],
+            layout_aware_keys: false,
+            poll_key_state: false,
+            gamepads,
             cursor_pos: (0., 0.),
             cursor: (MouseCursor::Arrow, None),
+            cursor_mode: None,
+            os_cursor_visible: true,
+            custom_cursor: None,
+            custom_cursor_active: false,
+            requested_cursor: None,
+            headless: self.headless,
+            respect_app_cursor_mode: false,
+            app_cursor_disabled: false,
+            input_enabled: true,
+            iconified: window.as_ref().is_some_and(|window| window.is_iconified()),
+            maximized: window.as_ref().is_some_and(|window| window.is_maximized()),
+            last_modifiers_char: None,
+            last_captured_event: None,
+            display_size_override: None,
+            framebuffer_size_for_display: false,
+            mouse_scale: [1., 1.],
+            window_size,
+            framebuffer_size,
+            srgb: false,
+            font_scale,
+            ui_scale: 1.,
+            base_style: None,
+            auto_style_base_size: None,
+            auto_style_scale: 1.,
+            scroll_smoothing: 0.,
+            scroll_pending: [0.; 2],
+            shift_scroll_horizontal: false,
+            show_metrics: false,
+            typing: false,
+            text_input_active: false,
+            text_input_callback: None,
+            last_frame_stats: FrameStats::default(),
 
+            #[cfg(feature = "opengl")]
             renderer,
+            #[cfg(feature = "opengl")]
+            gl_version,
+            #[cfg(feature = "opengl")]
+            font_texture_id,
+            #[cfg(feature = "opengl")]
+            post_render: None,
+            #[cfg(feature = "opengl")]
+            rendering_enabled: true,
+            #[cfg(feature = "opengl")]
+            auto_dpi_fonts: None,
+            #[cfg(feature = "opengl")]
+            dpi_fonts_pending: false,
+            #[cfg(feature = "opengl")]
+            dpi_style_scale: 1.,
+            #[cfg(feature = "opengl")]
+            textures,
         }
     }
+}
+
+/// Input handling and rendering of imgui for a single glfw window.
+///
+/// Every window needs its own `ImguiGLFW` (and imgui context), see the `multi_window` example.
+pub struct ImguiGLFW {
+    window_ptr: *mut GLFWwindow,
+    platform: GlfwPlatform,
+    last_frame: Option<Instant>,
+    frame_active: bool,
+    delta_time: f32,
+    max_delta: f32,
+    mouse_press: [bool; 5],
+    mouse_just_pressed: [bool; 5],
+    mouse_changed: [Option<Instant>; 5],
+    button_mapping: [Option<usize>; 8],
+    layout_aware_keys: bool,
+    poll_key_state: bool,
+    gamepads: Vec<JoystickId>,
+    cursor_pos: (f64, f64),
+    cursor: (MouseCursor, Option<StandardCursor>),
+    cursor_mode: Option<glfw::CursorMode>,
+    os_cursor_visible: bool,
+    custom_cursor: Option<glfw::Cursor>,
+    custom_cursor_active: bool,
+    requested_cursor: Option<StandardCursor>,
+    headless: bool,
+    respect_app_cursor_mode: bool,
+    app_cursor_disabled: bool,
+    input_enabled: bool,
+    iconified: bool,
+    maximized: bool,
+    last_modifiers_char: Option<char>,
+    last_captured_event: Option<WindowEventKind>,
+    display_size_override: Option<[f32; 2]>,
+    framebuffer_size_for_display: bool,
+    mouse_scale: [f32; 2],
+    window_size: (i32, i32),
+    framebuffer_size: (i32, i32),
+    srgb: bool,
+    font_scale: f32,
+    ui_scale: f32,
+    base_style: Option<imgui::Style>,
+    auto_style_base_size: Option<[f32; 2]>,
+    auto_style_scale: f32,
+    scroll_smoothing: f32,
+    scroll_pending: [f32; 2],
+    shift_scroll_horizontal: bool,
+    show_metrics: bool,
+    typing: bool,
+    text_input_active: bool,
+    text_input_callback: Option<Box<dyn FnMut(bool)>>,
+    last_frame_stats: FrameStats,
+
+    #[cfg(feature = "opengl")]
+    renderer: Option<Renderer>,
+    #[cfg(feature = "opengl")]
+    gl_version: (u32, u32),
+    #[cfg(feature = "opengl")]
+    font_texture_id: TextureId,
+    #[cfg(feature = "opengl")]
+    post_render: Option<Box<dyn FnMut()>>,
+    #[cfg(feature = "opengl")]
+    rendering_enabled: bool,
+    #[cfg(feature = "opengl")]
+    auto_dpi_fonts: Option<(Vec<u8>, f32)>,
+    #[cfg(feature = "opengl")]
+    dpi_fonts_pending: bool,
+    #[cfg(feature = "opengl")]
+    dpi_style_scale: f32,
+    #[cfg(feature = "opengl")]
+    textures: Vec<TextureId>,
+}
+
+impl ImguiGLFW {
+    pub fn builder() -> ImguiGLFWBuilder {
+        ImguiGLFWBuilder::new()
+    }
+
+    #[cfg(feature = "opengl")]
+    pub fn new(imgui: &mut Context, window: &mut Window) -> Self {
+        ImguiGLFWBuilder::new().build(imgui, window)
+    }
+
+    /// Set up input, clipboard and cursor handling without creating a renderer.
+    ///
+    /// Render imgui yourself with `prepare_render` or `render_with`. `draw()` then only
+    /// updates the cursors and ends the frame.
+    pub fn new_input_only(imgui: &mut Context, window: &mut Window) -> Self {
+        let builder = ImguiGLFWBuilder::new();
+        #[cfg(feature = "opengl")]
+        let builder = builder.with_renderer(false);
+        builder.build(imgui, window)
+    }
 
+    /// Forward a glfw event to imgui.
+    ///
+    /// Events don't have to come from `glfw::flush_messages`. If your event loop is built
+    /// around callbacks, construct the matching `WindowEvent` in the callback instead:
+    ///
+    /// ```ignore
+    /// // e.g. inside your key callback
+    /// imgui_glfw.handle_event(&mut imgui, &WindowEvent::Key(key, scancode, action, modifiers));
+    /// ```
     pub fn handle_event(&mut self, imgui: &mut Context, event: &WindowEvent) {
+        self.process_event(imgui, event);
+    }
+
+    /// Forward a glfw event to imgui and return whether imgui captured it.
+    fn process_event(&mut self, imgui: &mut Context, event: &WindowEvent) -> bool {
+        let captured = self.input_enabled && Self::is_captured(imgui.io(), event);
+        if captured {
+            self.last_captured_event = WindowEventKind::of(event);
+        }
+
         match *event {
+            WindowEvent::Iconify(iconified) => {
+                self.iconified = iconified;
+            }
+            // The display size follows in the next `frame()`, it's read from the window.
+            WindowEvent::Maximize(maximized) => {
+                self.maximized = maximized;
+            }
+            // The fonts are rebuilt in the next `frame()`, which has access to the window.
+            #[cfg(feature = "opengl")]
+            WindowEvent::ContentScale(..) if self.auto_dpi_fonts.is_some() => {
+                self.dpi_fonts_pending = true;
+            }
+            _ if !self.input_enabled => {}
             WindowEvent::MouseButton(mouse_btn, action, _) => {
-                let index = match mouse_btn {
-                    MouseButton::Button1 => 0,
-                    MouseButton::Button2 => 1,
-                    MouseButton::Button3 => 2,
-                    MouseButton::Button4 => 3,
-                    MouseButton::Button5 => 4,
-                    _ => 0,
-                };
-                let press = action != Action::Release;
-                self.mouse_press[index] = press;
-                imgui.io_mut().mouse_down = self.mouse_press;
+                self.inject_mouse_button(imgui, mouse_btn, action);
             }
-            WindowEvent::CursorPos(w, h) => {
-                imgui.io_mut().mouse_pos = [w as f32, h as f32];
-                self.cursor_pos = (w, h);
+            // While the app uses the cursor for itself, e.g. for mouselook, it is left out.
+            WindowEvent::CursorPos(w, h) if !self.app_cursor_disabled => {
+                self.inject_mouse_move(imgui, w, h);
             }
-            WindowEvent::Scroll(_, d) => {
-                imgui.io_mut().mouse_wheel = d as f32;
+            WindowEvent::Scroll(x, y) => {
+                self.inject_scroll(imgui, x, y);
             }
             WindowEvent::Char(character) => {
-                imgui.io_mut().add_input_character(character);
+                // Already forwarded by the `CharModifiers` event sent right before it.
+                let forwarded = self.last_modifiers_char.take() == Some(character);
+                if !forwarded {
+                    self.inject_char(imgui, character);
+                }
+            }
+            // GLFW doesn't send `Char` for all characters typed with modifiers, e.g. AltGr
+            // combinations like `@` on some layouts, but it always sends `CharModifiers`.
+            // Alt alone is left out, it is used for menu shortcuts on Windows.
+            WindowEvent::CharModifiers(character, modifiers) => {
+                if modifiers.contains(Modifiers::Alt) && !modifiers.contains(Modifiers::Control) {
+                    return captured;
+                }
+                self.inject_char(imgui, character);
+                self.last_modifiers_char = Some(character);
+            }
+            WindowEvent::Key(key, scancode, action, modifier) => {
+                let key = if self.layout_aware_keys {
+                    Self::layout_key(key, scancode)
+                } else {
+                    key
+                };
+                self.inject_key(imgui, key, action, modifier);
+            }
+            _ => {
+                #[cfg(feature = "log")]
+                log::trace!("imgui-glfw-rs: unhandled event {:?}", event);
+            }
+        }
+
+        captured
+    }
+
+    /// Forward a batch of glfw events to imgui. Returns whether imgui captured any of them,
+    /// see `dispatch` for which events are captured.
+    ///
+    /// ```ignore
+    /// let events: Vec<_> = glfw::flush_messages(&events).map(|(_, event)| event).collect();
+    /// let captured = imgui_glfw.handle_events(&mut imgui, events.iter());
+    /// ```
+    pub fn handle_events<'a>(
+        &mut self,
+        imgui: &mut Context,
+        events: impl Iterator<Item = &'a WindowEvent>,
+    ) -> bool {
+        let mut captured = false;
+        for event in events {
+            captured |= self.process_event(imgui, event);
+        }
+        captured
+    }
+
+    /// Forward a glfw event to imgui and to `forward` if imgui didn't capture it.
+    ///
+    /// - Mouse buttons, cursor movement and scrolling are captured while
+    ///   `io.want_capture_mouse` is set, e.g. while the mouse is over an imgui window.
+    /// - Keys and characters are captured while `io.want_capture_keyboard` is set,
+    ///   e.g. while a text field has focus.
+    /// - All other events are always forwarded.
+    ///
+    /// The capture flags are the ones of the last frame. Nothing is captured while input
+    /// is disabled with `set_input_enabled(false)`.
+    pub fn dispatch<F: FnMut(&WindowEvent)>(
+        &mut self,
+        imgui: &mut Context,
+        event: &WindowEvent,
+        mut forward: F,
+    ) {
+        if !self.process_event(imgui, event) {
+            forward(event);
+        }
+    }
+
+    /// Keep track of connected gamepads, call this from glfw's joystick callback.
+    /// Joysticks without a gamepad mapping are ignored, imgui can't navigate with them.
+    ///
+    /// `BackendFlags::HAS_GAMEPAD` is set while at least one gamepad is connected. When the
+    /// last one is removed, `io.nav_inputs` is cleared so stale axis values don't keep
+    /// navigating.
+    pub fn handle_joystick_event(
+        &mut self,
+        imgui: &mut Context,
+        joystick: JoystickId,
+        event: JoystickEvent,
+    ) {
+        match event {
+            JoystickEvent::Connected => {
+                // The callback has no `Glfw` to ask, but glfw is initialized when it runs.
+                let is_gamepad = unsafe {
+                    glfw::ffi::glfwJoystickIsGamepad(joystick as c_int) == glfw::ffi::TRUE
+                };
+                if is_gamepad && !self.gamepads.contains(&joystick) {
+                    self.gamepads.push(joystick);
+                }
+            }
+            JoystickEvent::Disconnected => {
+                self.gamepads.retain(|&gamepad| gamepad != joystick);
+            }
+        }
+
+        let io = imgui.io_mut();
+        let connected = !self.gamepads.is_empty();
+        io.backend_flags.set(BackendFlags::HAS_GAMEPAD, connected);
+        if !connected {
+            for input in io.nav_inputs.iter_mut() {
+                *input = 0.;
             }
-            WindowEvent::Key(key, _, action, modifier) => {
-                Self::set_mod(imgui, modifier);
-                imgui.io_mut().keys_down[key as usize] = action != Action::Release;
+        }
+    }
+
+    /// Press or release a mouse button as if glfw reported it, e.g. for automated UI tests.
+    ///
+    /// A press that is released before the next `frame()` stays down until then, otherwise
+    /// imgui would miss quick clicks.
+    pub fn inject_mouse_button(
+        &mut self,
+        imgui: &mut Context,
+        button: MouseButton,
+        action: Action,
+    ) {
+        let index = match self.button_mapping[button as usize] {
+            Some(index) => index,
+            None => return,
+        };
+        let press = action != Action::Release;
+        if self.mouse_press[index] != press {
+            self.mouse_changed[index] = Some(Instant::now());
+        }
+        self.mouse_press[index] = press;
+        // Keep presses that are released before the next frame visible to imgui.
+        self.mouse_just_pressed[index] |= press;
+        imgui.io_mut().mouse_down[index] = press || self.mouse_just_pressed[index];
+    }
+
+    /// Move the mouse to a position in window coordinates as if glfw reported it.
+    pub fn inject_mouse_move(&mut self, imgui: &mut Context, x: f64, y: f64) {
+        imgui.io_mut().mouse_pos = self.to_imgui_mouse_pos(x, y);
+        self.cursor_pos = (x, y);
+    }
+
+    /// Scroll as if glfw reported it.
+    pub fn inject_scroll(&mut self, imgui: &mut Context, x: f64, y: f64) {
+        // Some drivers report NaN or huge deltas, which break imgui's scroll state.
+        let (x, y) = (Self::sanitize_scroll(x), Self::sanitize_scroll(y));
+        let (x, y) = if self.shift_scroll_horizontal && imgui.io().key_shift {
+            (x + y, 0.)
+        } else {
+            (x, y)
+        };
+        if self.scroll_smoothing > 0. {
+            self.scroll_pending[0] += x;
+            self.scroll_pending[1] += y;
+        } else {
+            // Several scroll events can arrive within a frame, imgui resets the wheel
+            // after every frame.
+            imgui.io_mut().mouse_wheel_h += x;
+            imgui.io_mut().mouse_wheel += y;
+        }
+    }
+
+    /// Type a character as if glfw reported it.
+    pub fn inject_char(&mut self, imgui: &mut Context, character: char) {
+        imgui.io_mut().add_input_character(character);
+    }
+
+    /// Press or release a key as if glfw reported it.
+    pub fn inject_key(
+        &mut self,
+        imgui: &mut Context,
+        key: Key,
+        action: Action,
+        modifiers: Modifiers,
+    ) {
+        let mut modifiers = modifiers;
+        // Platforms differ in whether a modifier key's own press and release events
+        // include the modifier, so derive it from the key to keep combos reliable.
+        if let Some(modifier) = Self::key_modifier(key) {
+            match action {
+                Action::Press => modifiers.insert(modifier),
+                Action::Release => modifiers.remove(modifier),
+                Action::Repeat => {}
             }
-            _ => {}
         }
+        Self::set_mod(imgui, modifiers);
+
+        // `Key::Unknown` is -1, imgui has no slot for it.
+        let down = match imgui.io_mut().keys_down.get_mut(key as usize) {
+            Some(down) => down,
+            None => return,
+        };
+        // imgui repeats held keys on its own, `Action::Repeat` doesn't change the state.
+        match action {
+            Action::Press => *down = true,
+            Action::Release => *down = false,
+            Action::Repeat => {}
+        }
+    }
+
+    /// Release all mouse buttons, keys and modifiers, e.g. when switching between app states.
+    pub fn clear_input_state(&mut self, imgui: &mut Context) {
+        self.mouse_press = [false; 5];
+        self.mouse_just_pressed = [false; 5];
+
+        let io = imgui.io_mut();
+        io.mouse_down = [false; 5];
+        for down in io.keys_down.iter_mut() {
+            *down = false;
+        }
+        Self::set_mod(imgui, Modifiers::empty());
     }
 
     pub fn frame<'a>(&mut self, window: &mut Window, imgui: &'a mut Context) -> imgui::Ui<'a> {
+        self.begin_frame(window, imgui);
+
+        let ui = imgui.frame();
+        if self.show_metrics {
+            ui.show_metrics_window(&mut self.show_metrics);
+        }
+        ui
+    }
+
+    /// Prepare imgui's io for a new frame without starting it.
+    /// Call `imgui.frame()` yourself afterwards, the returned `Ui` then doesn't borrow `self`.
+    pub fn begin_frame(&mut self, window: &mut Window, imgui: &mut Context) {
+        let (width, height) = window.get_size();
+        self.set_window_size(width, height);
+        let (width, height) = window.get_framebuffer_size();
+        self.set_framebuffer_size(width, height);
+        self.app_cursor_disabled =
+            self.respect_app_cursor_mode && window.get_cursor_mode() == glfw::CursorMode::Disabled;
+
+        self.begin_frame_detached(imgui);
+
+        #[cfg(feature = "opengl")]
+        {
+            if self.dpi_fonts_pending {
+                self.dpi_fonts_pending = false;
+                self.rebuild_dpi_fonts(imgui, window);
+            }
+        }
+
+        let io = imgui.io_mut();
+
+        if self.poll_key_state && self.input_enabled {
+            for &(_, key) in KEY_MAPPING.iter() {
+                // glfw reports physical keys, polling would undo the layout translation.
+                if self.layout_aware_keys && Self::is_letter_key(key) {
+                    continue;
+                }
+                io.keys_down[key as usize] = window.get_key(key) != Action::Release;
+            }
+            let down = |left, right| {
+                window.get_key(left) != Action::Release || window.get_key(right) != Action::Release
+            };
+            io.key_ctrl = down(Key::LeftControl, Key::RightControl);
+            io.key_alt = down(Key::LeftAlt, Key::RightAlt);
+            io.key_shift = down(Key::LeftShift, Key::RightShift);
+            io.key_super = down(Key::LeftSuper, Key::RightSuper);
+        }
+
+        // Keyboard and gamepad navigation may ask to move the cursor.
+        if io.want_set_mouse_pos
+            && !self.headless
+            && !self.app_cursor_disabled
+            && self.platform.can_set_mouse_pos()
+        {
+            let [x, y] = io.mouse_pos;
+            window.set_cursor_pos(
+                f64::from(x / self.mouse_scale[0]),
+                f64::from(y / self.mouse_scale[1]),
+            );
+        }
+
+        // GLFW may stop reporting cursor movement outside the window, keep drags going.
+        if self.input_enabled
+            && !self.app_cursor_disabled
+            && self.mouse_press.iter().any(|&press| press)
+        {
+            let (x, y) = window.get_cursor_pos();
+            io.mouse_pos = self.to_imgui_mouse_pos(x, y);
+            self.cursor_pos = (x, y);
+        }
+    }
+
+    /// Like `begin_frame`, but uses the sizes passed to `set_window_size` and
+    /// `set_framebuffer_size` instead of querying the window.
+    ///
+    /// This lets a UI thread assemble frames from events forwarded by the main thread.
+    /// GLFW itself must only be used on the main thread, so cursor changes, cursor warps
+    /// and cursor polling during drags are left out. `ImguiGLFW` itself is not `Send`
+    /// (it holds glfw and GL handles) and has to be created on the thread that uses it.
+    pub fn begin_frame_detached(&mut self, imgui: &mut Context) {
+        self.frame_active = true;
         let io = imgui.io_mut();
 
         let now = Instant::now();
-        let delta = now - self.last_frame;
-        let delta_s = delta.as_secs() as f32 + delta.subsec_nanos() as f32 / 1_000_000_000.0;
-        self.last_frame = now;
+        let delta_s = Self::frame_delta(self.last_frame, now, self.max_delta);
+        self.last_frame = Some(now);
+        self.delta_time = delta_s;
         io.delta_time = delta_s;
 
-        let window_size = window.get_size();
-        io.display_size = [window_size.0 as f32, window_size.1 as f32];
+        // GLFW reports the cursor in window coordinates. By default imgui works in window
+        // coordinates as well and the renderer scales up to framebuffer pixels.
+        let window_size = self.window_size;
+        let framebuffer_size = self.framebuffer_size;
+        let framebuffer_scale = if window_size.0 > 0 && window_size.1 > 0 {
+            Some([
+                framebuffer_size.0 as f32 / window_size.0 as f32,
+                framebuffer_size.1 as f32 / window_size.1 as f32,
+            ])
+        } else {
+            None
+        };
+
+        if self.framebuffer_size_for_display {
+            io.display_size = self
+                .display_size_override
+                .unwrap_or([framebuffer_size.0 as f32, framebuffer_size.1 as f32]);
+            io.display_framebuffer_scale = [1., 1.];
+            if let Some(framebuffer_scale) = framebuffer_scale {
+                self.mouse_scale = framebuffer_scale;
+            }
+        } else {
+            io.display_size = self
+                .display_size_override
+                .unwrap_or([window_size.0 as f32, window_size.1 as f32]);
+            if let Some(framebuffer_scale) = framebuffer_scale {
+                io.display_framebuffer_scale = framebuffer_scale;
+            }
+            self.mouse_scale = [1., 1.];
+        }
+
+        for (index, down) in io.mouse_down.iter_mut().enumerate() {
+            *down = self.mouse_press[index] || self.mouse_just_pressed[index];
+        }
+        self.mouse_just_pressed = [false; 5];
+
+        if self.scroll_smoothing > 0. {
+            let mut wheel = [0.; 2];
+            for (pending, wheel) in self.scroll_pending.iter_mut().zip(wheel.iter_mut()) {
+                let (step, rest) = Self::smooth_scroll_step(*pending, self.scroll_smoothing);
+                *wheel = step;
+                *pending = rest;
+            }
+            io.mouse_wheel_h = wheel[0];
+            io.mouse_wheel = wheel[1];
+        }
+
+        let want_text_input = io.want_text_input;
+        if want_text_input != self.text_input_active {
+            self.text_input_active = want_text_input;
+            if let Some(callback) = self.text_input_callback.as_mut() {
+                callback(want_text_input);
+            }
+        }
 
-        imgui.frame()
+        self.update_auto_style_scale(imgui);
     }
 
+    /// Update the cursors and draw the frame into the currently bound framebuffer.
+    ///
+    /// The UI is drawn on top of the scene regardless of its depth: the renderer disables
+    /// depth testing (and face culling) while drawing and restores the previous state.
+    #[cfg(feature = "opengl")]
     pub fn draw<'ui>(&mut self, ui: Ui<'ui>, window: &mut Window) {
-        let io = ui.io();
-        if !io
-            .config_flags
-            .contains(ConfigFlags::NO_MOUSE_CURSOR_CHANGE)
-        {
-            match ui.mouse_cursor() {
-                Some(mouse_cursor) if !io.mouse_draw_cursor => {
-                    window.set_cursor_mode(glfw::CursorMode::Normal);
-
-                    let cursor = match mouse_cursor {
-                        MouseCursor::TextInput => StandardCursor::IBeam,
-                        MouseCursor::ResizeNS => StandardCursor::VResize,
-                        MouseCursor::ResizeEW => StandardCursor::HResize,
-                        MouseCursor::Hand => StandardCursor::Hand,
-                        _ => StandardCursor::Arrow,
-                    };
-                    window.set_cursor(Some(glfw::Cursor::standard(cursor)));
-
-                    if self.cursor.1 != Some(cursor) {
-                        self.cursor.1 = Some(cursor);
-                        self.cursor.0 = mouse_cursor;
-                    }
-                }
-                _ => {
-                    self.cursor.0 = MouseCursor::Arrow;
-                    self.cursor.1 = None;
-                    window.set_cursor_mode(glfw::CursorMode::Hidden);
-                }
+        self.draw_for(ui, window);
+    }
+
+    #[cfg(feature = "opengl")]
+    fn draw_for<'ui, W: CursorWindow>(&mut self, ui: Ui<'ui>, window: &mut W) {
+        if !self.begin_render(&ui, window) {
+            return;
+        }
+
+        let render_start = Instant::now();
+        match self.renderer.as_mut() {
+            Some(renderer) if self.rendering_enabled => {
+                renderer.render(ui);
+            }
+            _ => {
+                ui.render();
+            }
+        }
+        let render_time = render_start.elapsed();
+
+        // Nothing was drawn for the hook to draw on top of or read back.
+        if self.rendering_enabled {
+            if let Some(post_render) = self.post_render.as_mut() {
+                post_render();
             }
         }
 
-        self.renderer.render(ui);
+        // The renderer consumes the `Ui`, but the draw data stays valid until the next frame.
+        self.update_frame_stats(render_time);
     }
 
-    fn set_mod(imgui: &mut Context, modifier: Modifiers) {
-        imgui.io_mut().key_ctrl = modifier.intersects(Modifiers::Control);
-        imgui.io_mut().key_alt = modifier.intersects(Modifiers::Alt);
-        imgui.io_mut().key_shift = modifier.intersects(Modifiers::Shift);
-        imgui.io_mut().key_super = modifier.intersects(Modifiers::Super);
+    /// Draw into the framebuffer object `fbo` instead of the currently bound framebuffer,
+    /// e.g. to composite the UI later. The previous framebuffer is bound again afterwards.
+    ///
+    /// The renderer sets the viewport and scissor from imgui's display size and framebuffer
+    /// scale. Match them to the size of `fbo` with `use_framebuffer_size_for_display(true)`
+    /// and `set_display_size_override`.
+    #[cfg(feature = "opengl")]
+    pub fn draw_to_framebuffer<'ui>(&mut self, ui: Ui<'ui>, window: &mut Window, fbo: u32) {
+        let mut previous_fbo = 0;
+        unsafe {
+            gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut previous_fbo);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+        }
+
+        self.draw(ui, window);
+
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, previous_fbo as u32);
+        }
+    }
+
+    /// Update the cursors and render the frame without drawing it.
+    /// Use this to hand the draw data to your own renderer instead of `draw()`.
+    ///
+    /// Returns `None` when there is nothing to draw, e.g. while the window is minimized.
+    pub fn prepare_render<'ui>(
+        &mut self,
+        ui: Ui<'ui>,
+        window: &mut Window,
+    ) -> Option<&'ui DrawData> {
+        self.prepare_render_for(ui, window)
+    }
+
+    fn prepare_render_for<'ui, W: CursorWindow>(
+        &mut self,
+        ui: Ui<'ui>,
+        window: &mut W,
+    ) -> Option<&'ui DrawData> {
+        if !self.begin_render(&ui, window) {
+            return None;
+        }
+
+        let draw_data = ui.render();
+        self.update_frame_stats(Duration::default());
+        Some(draw_data)
+    }
+
+    /// Update the cursors, render the frame and pass the draw data to `draw`,
+    /// e.g. to draw it with a Vulkan or wgpu renderer.
+    ///
+    /// Like `draw()`, this must be called on the main thread because it changes the
+    /// cursor of the window. `draw` runs right away on the same thread, unless there is
+    /// nothing to draw (see `prepare_render`).
+    pub fn render_with<'ui, F: FnOnce(&DrawData)>(
+        &mut self,
+        ui: Ui<'ui>,
+        window: &mut Window,
+        draw: F,
+    ) {
+        self.with_draw_data(ui, window, draw);
+    }
+
+    /// Like `render_with`, but returns the result of `f`,
+    /// e.g. to inspect vertex and index counts in tests.
+    pub fn with_draw_data<'ui, R>(
+        &mut self,
+        ui: Ui<'ui>,
+        window: &mut Window,
+        f: impl FnOnce(&DrawData) -> R,
+    ) -> Option<R> {
+        self.prepare_render(ui, window).map(f)
+    }
+
+    /// Read `width` x `height` pixels of the bound framebuffer as RGBA, top row first,
+    /// e.g. for a screenshot after `draw()` and before swapping buffers.
+    ///
+    /// This waits for the GPU to finish rendering, so don't call it every frame.
+    #[cfg(feature = "opengl")]
+    pub fn capture_framebuffer(&self, width: u32, height: u32) -> Vec<u8> {
+        let row_len = width as usize * 4;
+        let mut pixels = vec![0u8; row_len * height as usize];
+        unsafe {
+            gl::ReadPixels(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut c_void,
+            );
+        }
+
+        // OpenGL returns the bottom row first.
+        let mut flipped = Vec::with_capacity(pixels.len());
+        for row in pixels.chunks(row_len.max(1)).rev() {
+            flipped.extend_from_slice(row);
+        }
+        flipped
+    }
+
+    /// Use a fixed display size instead of the window size, e.g. to render into a sub-region.
+    ///
+    /// Mouse positions are still passed to imgui relative to the top left corner of the
+    /// window and `display_framebuffer_scale` is applied on top of this size, so the override
+    /// should be given in window coordinates (framebuffer pixels with
+    /// `use_framebuffer_size_for_display`).
+    pub fn set_display_size_override(&mut self, size: Option<[f32; 2]>) {
+        self.display_size_override = size;
+    }
+
+    /// Show `cursor` in the current frame, e.g. a crosshair while hovering a canvas.
+    ///
+    /// Only applies while imgui wants the default arrow, cursors imgui asks for itself
+    /// (text input, resize borders, ...) take precedence. Call this every frame before `draw()`.
+    pub fn request_cursor(&mut self, cursor: StandardCursor) {
+        self.requested_cursor = Some(cursor);
+    }
+
+    /// Show a custom cursor image instead of the cursors requested by imgui.
+    /// `hot` is the position of the cursor's hotspot in the image.
+    pub fn set_custom_cursor(&mut self, image: glfw::PixelImage, hot: (u32, u32)) {
+        self.custom_cursor = Some(glfw::Cursor::create_from_pixels(image, hot.0, hot.1));
+        self.custom_cursor_active = true;
+    }
+
+    /// Hide the OS cursor regardless of the cursor imgui wants, e.g. when the app draws its
+    /// own cursor sprite. `current_cursor` still reports the cursor imgui wants.
+    ///
+    /// Like all cursor changes, this has no effect while `NO_MOUSE_CURSOR_CHANGE` is set.
+    pub fn set_os_cursor_visible(&mut self, visible: bool) {
+        self.os_cursor_visible = visible;
+    }
+
+    /// Go back to the cursors requested by imgui.
+    pub fn clear_custom_cursor(&mut self) {
+        self.custom_cursor = None;
+        self.custom_cursor_active = false;
+    }
+
+    /// Scale the whole UI, e.g. `1.25` for a 125% UI scale preference.
+    ///
+    /// This sets `io.font_global_scale` (on top of the content scale applied at construction)
+    /// and scales the style sizes from the style as it was before the first scaling, so set
+    /// up custom sizes before calling this. The framebuffer scale is left alone, it is
+    /// derived from the window. Scaled fonts look blurry, for crisp text rebuild the fonts
+    /// at the scaled size instead and reset `io.font_global_scale` to `1.0`.
+    pub fn set_ui_scale(&mut self, imgui: &mut Context, scale: f32) {
+        if scale <= 0. || !scale.is_finite() {
+            return;
+        }
+
+        imgui.io_mut().font_global_scale = scale * self.font_scale;
+        self.ui_scale = scale;
+        self.rescale_style(imgui);
+    }
+
+    /// Scale the style sizes (padding, rounding, ...) with the display size relative to
+    /// `base_size`, the display size the style was designed for. `None` turns it off again.
+    ///
+    /// The style is scaled in `frame()` whenever the display size changes. This composes with
+    /// `set_ui_scale`, the resulting sizes are the product of both scales.
+    pub fn set_auto_style_scale(&mut self, base_size: Option<[f32; 2]>) {
+        self.auto_style_base_size = base_size;
+    }
+
+    /// Smooth scrolling by spreading wheel input over several frames, e.g. for trackpads.
+    ///
+    /// Every frame `1.0 - factor` of the remaining scroll distance is passed to imgui, so
+    /// the total always adds up to the input. `0.0` (default) passes scrolling through
+    /// unchanged, values closer to `1.0` smooth more.
+    pub fn set_scroll_smoothing(&mut self, factor: f32) {
+        self.scroll_smoothing = if factor.is_nan() {
+            0.
+        } else {
+            factor.clamp(0., 0.99)
+        };
+        if self.scroll_smoothing == 0. {
+            self.scroll_pending = [0.; 2];
+        }
+    }
+
+    /// Scroll horizontally with the vertical wheel while Shift is held, like most desktop
+    /// apps do. Off by default, some platforms already convert Shift+wheel themselves.
+    pub fn set_shift_scroll_horizontal(&mut self, enabled: bool) {
+        self.shift_scroll_horizontal = enabled;
+    }
+
+    /// The window size used by `begin_frame_detached`, `begin_frame` sets it from the window.
+    pub fn set_window_size(&mut self, width: i32, height: i32) {
+        self.window_size = (width, height);
+    }
+
+    /// The framebuffer size used by `begin_frame_detached`, `begin_frame` sets it from the
+    /// window.
+    pub fn set_framebuffer_size(&mut self, width: i32, height: i32) {
+        self.framebuffer_size = (width, height);
+    }
+
+    /// Use the framebuffer size instead of the window size as imgui's display size.
+    ///
+    /// By default imgui works in window coordinates and `display_framebuffer_scale` maps
+    /// them to pixels, which is what you want on macOS style HiDPI displays. When GLFW
+    /// scales the whole window (`ScaleToMonitor`), enable this so imgui works in
+    /// framebuffer pixels with a scale of `1.0`. Mouse positions are converted to match.
+    pub fn use_framebuffer_size_for_display(&mut self, enabled: bool) {
+        self.framebuffer_size_for_display = enabled;
+    }
+
+    /// Enable this when rendering into an sRGB framebuffer (`GL_FRAMEBUFFER_SRGB`).
+    ///
+    /// imgui's colors are sRGB values. An sRGB framebuffer treats them as linear and
+    /// converts them once more, which makes the UI look washed out. When enabled, the style
+    /// colors are converted to linear so they end up unchanged on screen. Colors of user
+    /// textures and colors pushed per widget are not converted.
+    pub fn set_srgb(&mut self, imgui: &mut Context, srgb: bool) {
+        if self.srgb == srgb {
+            return;
+        }
+        self.srgb = srgb;
+
+        let convert = if srgb { srgb_to_linear } else { linear_to_srgb };
+        Self::convert_style_colors(imgui, convert);
+        imgui.io_mut().config_flags.set(ConfigFlags::IS_SRGB, srgb);
+    }
+
+    /// Switch to one of imgui's color styles, e.g. for a light/dark theme toggle.
+    ///
+    /// Only the style colors are replaced, so sizes scaled by `set_ui_scale` and
+    /// `set_auto_style_scale` are kept and the order of the calls doesn't matter.
+    /// The colors are converted for sRGB framebuffers if `set_srgb` is enabled.
+    pub fn set_theme(&mut self, imgui: &mut Context, theme: Theme) {
+        let style = imgui.style_mut();
+        match theme {
+            Theme::Dark => style.use_dark_colors(),
+            Theme::Light => style.use_light_colors(),
+            Theme::Classic => style.use_classic_colors(),
+        };
+        if self.srgb {
+            Self::convert_style_colors(imgui, srgb_to_linear);
+        }
+    }
+
+    /// Rebuild the font atlas and upload it again, e.g. after adding fonts or changing the DPI.
+    ///
+    /// This recreates the OpenGL renderer, so it must be called on the thread
+    /// that owns the window's GL context while that context is current.
+    /// Without a renderer (`new_input_only`) this does nothing.
+    #[cfg(feature = "opengl")]
+    pub fn reload_fonts(&mut self, imgui: &mut Context, window: &mut Window) {
+        if self.renderer.is_some() {
+            self.renderer = Some(Renderer::new(imgui, |s| window.get_proc_address(s) as _));
+            self.font_texture_id = imgui.fonts().tex_id;
+        }
+    }
+
+    /// Rebuild the fonts for the window's content scale whenever it changes, e.g. when the
+    /// window is moved to a monitor with a different DPI. `None` turns it off again.
+    ///
+    /// The font atlas is rebuilt from `font_data` (a TTF file) at `base_size` times the
+    /// content scale in the first `frame()` and after every `ContentScale` event, and the
+    /// style sizes are scaled along. Other fonts in the atlas are removed. Every rebuild
+    /// uploads a new font texture, so it happens at most once per frame.
+    #[cfg(feature = "opengl")]
+    pub fn set_auto_dpi_fonts(&mut self, font_data: Option<Vec<u8>>, base_size: f32) {
+        self.auto_dpi_fonts = font_data.map(|font_data| (font_data, base_size));
+        self.dpi_fonts_pending = self.auto_dpi_fonts.is_some();
+    }
+
+    /// Add the default font with the glyphs of an icon font (e.g. FontAwesome) merged into it
+    /// and reload the font atlas.
+    ///
+    /// `glyph_ranges` are pairs of inclusive UCS-2 codepoint ranges terminated by a zero,
+    /// e.g. `&[0xf000, 0xf3ff, 0]`.
+    #[cfg(feature = "opengl")]
+    pub fn add_font_with_icons(
+        &mut self,
+        imgui: &mut Context,
+        window: &mut Window,
+        icon_data: &[u8],
+        size_pixels: f32,
+        glyph_ranges: &'static [u16],
+    ) -> FontId {
+        let font = imgui.fonts().add_font(&[
+            FontSource::DefaultFontData {
+                config: Some(FontConfig {
+                    size_pixels,
+                    ..FontConfig::default()
+                }),
+            },
+            FontSource::TtfData {
+                data: icon_data,
+                size_pixels,
+                config: Some(FontConfig {
+                    glyph_ranges: FontGlyphRanges::from_slice(glyph_ranges),
+                    ..FontConfig::default()
+                }),
+            },
+        ]);
+        self.reload_fonts(imgui, window);
+        font
+    }
+
+    /// The OpenGL version of the context the renderer was created with, `(0, 0)` without a
+    /// renderer.
+    #[cfg(feature = "opengl")]
+    pub fn gl_version(&self) -> (u32, u32) {
+        self.gl_version
+    }
+
+    /// The texture of the font atlas uploaded by the renderer, e.g. to show the atlas with
+    /// `Image` while debugging fonts. It changes with every `reload_fonts`.
+    #[cfg(feature = "opengl")]
+    pub fn font_texture_id(&self) -> TextureId {
+        self.font_texture_id
+    }
+
+    /// Get a `TextureId` to show an OpenGL texture with `Image` or `ImageButton`.
+    ///
+    /// The texture is not owned by imgui and must stay alive as long as it is drawn.
+    #[cfg(feature = "opengl")]
+    pub fn register_texture(&mut self, gl_texture_id: u32) -> TextureId {
+        // The OpenGL renderer binds texture ids directly as GL texture names.
+        TextureId::from(gl_texture_id as usize)
+    }
+
+    /// The `TextureId`s of the textures passed to `ImguiGLFWBuilder::with_textures`.
+    #[cfg(feature = "opengl")]
+    pub fn textures(&self) -> &[TextureId] {
+        &self.textures
+    }
+
+    /// Stop using a texture registered with `register_texture`.
+    ///
+    /// The GL texture is not deleted, this is left to the owner of the texture.
+    #[cfg(feature = "opengl")]
+    pub fn unregister_texture(&mut self, _texture_id: TextureId) {}
+
+    /// When the mouse button with imgui index `button` (0 to 4) was last pressed or released.
+    pub fn last_button_change(&self, button: usize) -> Option<Instant> {
+        self.mouse_changed.get(button).and_then(|&changed| changed)
+    }
+
+    /// The last cursor position in window coordinates with full precision, e.g. for
+    /// drawing tools. imgui itself only gets it as `f32`.
+    pub fn cursor_pos_f64(&self) -> (f64, f64) {
+        self.cursor_pos
+    }
+
+    /// The cursor imgui wants and the OS cursor that was set for it by the last `draw()`.
+    /// The OS cursor is `None` while it is hidden or a custom cursor is shown.
+    pub fn current_cursor(&self) -> (MouseCursor, Option<StandardCursor>) {
+        self.cursor
+    }
+
+    /// Whether a text input widget had focus in the last rendered frame,
+    /// e.g. to ignore movement keys while the user is typing.
+    pub fn is_typing(&self) -> bool {
+        self.typing
+    }
+
+    /// Whether the user is interacting with imgui, e.g. to ignore clicks in the 3D view.
+    ///
+    /// This is `io.want_capture_mouse || io.want_capture_keyboard || igIsAnyItemActive()`,
+    /// so it also covers a widget being dragged outside of its window.
+    pub fn is_gui_interacting(&self, imgui: &Context) -> bool {
+        let io = imgui.io();
+        io.want_capture_mouse
+            || io.want_capture_keyboard
+            || unsafe { imgui::sys::igIsAnyItemActive() }
+    }
+
+    /// Call `callback` from `frame()` whenever imgui starts or stops wanting text input,
+    /// e.g. to show and hide an on-screen keyboard.
+    pub fn set_text_input_callback(&mut self, callback: Option<Box<dyn FnMut(bool)>>) {
+        self.text_input_callback = callback;
+    }
+
+    /// The kind of the last event imgui captured, e.g. to find out why a key press never
+    /// reached the app. Like `dispatch`, this uses the capture flags of the last frame.
+    pub fn last_captured_event(&self) -> Option<WindowEventKind> {
+        self.last_captured_event
+    }
+
+    /// Call `post_render` at the end of every `draw()`, right after imgui was drawn,
+    /// e.g. to draw an overlay on top of the UI or to read back the framebuffer.
+    ///
+    /// The renderer restores the GL state it changed, so the hook sees the same state
+    /// (program, textures, buffers, blending, scissor, viewport) as before `draw()`.
+    #[cfg(feature = "opengl")]
+    pub fn set_post_render(&mut self, post_render: Option<Box<dyn FnMut()>>) {
+        self.post_render = post_render;
+    }
+
+    /// Geometry and timing of the last rendered frame.
+    pub fn last_frame_stats(&self) -> FrameStats {
+        self.last_frame_stats
+    }
+
+    /// Whether the window is maximized, e.g. to store it alongside `imgui.ini`.
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// The raw glfw window this `ImguiGLFW` was built for, e.g. for native FFI extensions.
+    ///
+    /// The pointer is only valid while the `Window` is alive. It aliases the `Window`, so
+    /// don't use it while you hold a borrow of the window, and only on the main thread.
+    pub fn glfw_window_ptr(&self) -> *mut GLFWwindow {
+        self.window_ptr
+    }
+
+    /// The windowing platform GLFW was built for.
+    pub fn platform(&self) -> GlfwPlatform {
+        self.platform
+    }
+
+    /// The delta time in seconds that was passed to imgui by the last `frame()`.
+    /// It is close to zero for the first frame.
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    /// Leave the cursor alone while the app has set `CursorMode::Disabled`, e.g. for an FPS
+    /// camera. imgui's mouse position is frozen until the cursor is enabled again.
+    pub fn set_respect_app_cursor_mode(&mut self, respect: bool) {
+        self.respect_app_cursor_mode = respect;
+    }
+
+    /// Remap mouse buttons, `map[n]` is the imgui button index for glfw's `Button{n+1}`,
+    /// e.g. `[0, 2, 1, 3, 4]` swaps the right and middle button. The default is the identity.
+    /// Indices above 4 are clamped, imgui only knows 5 mouse buttons.
+    pub fn set_button_mapping(&mut self, map: [usize; 5]) {
+        for (mapping, &index) in self.button_mapping.iter_mut().zip(map.iter()) {
+            *mapping = Some(index.min(4));
+        }
+    }
+
+    /// Map any glfw mouse button to an imgui button index (0 to 4), e.g. a thumb button
+    /// (`Button6` and up) to the middle button. `None` ignores the button.
+    ///
+    /// imgui only knows 5 mouse buttons, indices above 4 are clamped. By default
+    /// `Button6` to `Button8` are ignored.
+    pub fn map_mouse_button(&mut self, button: MouseButton, index: Option<usize>) {
+        self.button_mapping[button as usize] = index.map(|index| index.min(4));
+    }
+
+    /// Show imgui's metrics window in every `frame()`, e.g. bound to a debug hotkey.
+    /// Closing the window turns this off again.
+    pub fn set_show_metrics(&mut self, show: bool) {
+        self.show_metrics = show;
+    }
+
+    /// Limit the delta time passed to imgui, in seconds. The default is `0.1`.
+    ///
+    /// imgui needs a positive delta, values that aren't finite and positive are ignored.
+    pub fn set_max_delta(&mut self, max_delta: f32) {
+        if max_delta.is_finite() && max_delta > 0. {
+            self.max_delta = max_delta;
+        }
+    }
+
+    /// Translate letter keys by the keyboard layout instead of their position.
+    ///
+    /// glfw keys are named after their position on a US keyboard, so by default imgui's
+    /// shortcuts (Ctrl+A, Ctrl+C, Ctrl+V, ...) stay on the same physical keys on AZERTY or
+    /// Dvorak. That is what games want. Enable this for text tools, so the shortcuts follow
+    /// the letters printed on the user's keys.
+    pub fn set_layout_aware_keys(&mut self, layout_aware: bool) {
+        self.layout_aware_keys = layout_aware;
+    }
+
+    /// Read the state of the keys imgui uses from the window in every `frame()`, in addition
+    /// to the key events.
+    ///
+    /// Enable this together with GLFW's sticky keys (`window.set_sticky_keys(true)`) when
+    /// the app doesn't poll events every frame. With sticky keys a key pressed and released
+    /// between two frames still reads as pressed once, so imgui sees the press, and the
+    /// release follows in the next frame.
+    ///
+    /// With `set_layout_aware_keys` enabled, letter keys are left to the key events, glfw
+    /// can only be polled for the physical keys.
+    pub fn set_poll_key_state(&mut self, poll: bool) {
+        self.poll_key_state = poll;
+    }
+
+    /// Skip drawing in `draw()`, e.g. while the window is occluded. The frame is still
+    /// rendered by imgui, so hover and active states stay up to date.
+    ///
+    /// Unlike `set_input_enabled(false)`, imgui keeps receiving events. The
+    /// `set_post_render` hook is skipped too, there is no frame for it to work on.
+    #[cfg(feature = "opengl")]
+    pub fn set_rendering_enabled(&mut self, enabled: bool) {
+        self.rendering_enabled = enabled;
+    }
+
+    /// Stop or resume forwarding events to imgui, e.g. while a native dialog is open.
+    /// `frame()` still updates the delta time and display size while input is disabled.
+    ///
+    /// Disabling input releases all held mouse buttons, keys and modifiers, their release
+    /// events would be lost otherwise.
+    pub fn set_input_enabled(&mut self, imgui: &mut Context, enabled: bool) {
+        if self.input_enabled && !enabled {
+            self.clear_input_state(imgui);
+        }
+        self.input_enabled = enabled;
+    }
+
+    /// imgui's own error for a frame without display size or delta time is hard to trace
+    /// back to a missing `frame()` call.
+    fn end_frame(&mut self) {
+        debug_assert!(
+            self.frame_active,
+            "imgui-glfw-rs: draw() called before frame()/begin_frame()"
+        );
+        self.frame_active = false;
+    }
+
+    /// `DrawData` doesn't expose the number of draw lists, read it from imgui directly.
+    fn update_frame_stats(&mut self, render_time: Duration) {
+        let draw_data = unsafe { imgui::sys::igGetDrawData() };
+        if let Some(draw_data) = unsafe { draw_data.as_ref() } {
+            self.last_frame_stats = FrameStats {
+                draw_list_count: draw_data.CmdListsCount as usize,
+                vertex_count: draw_data.TotalVtxCount as usize,
+                index_count: draw_data.TotalIdxCount as usize,
+                render_time,
+            };
+        }
+    }
+
+    fn update_cursors<W: CursorWindow>(&mut self, ui: &Ui, window: &mut W) {
+        let requested_cursor = self.requested_cursor.take();
+        if self.app_cursor_disabled {
+            // The app changes the cursor mode itself, set it again once it is done.
+            self.cursor_mode = None;
+        }
+        if self.headless || self.app_cursor_disabled {
+            return;
+        }
+
+        let io = ui.io();
+        if io
+            .config_flags
+            .contains(ConfigFlags::NO_MOUSE_CURSOR_CHANGE)
+        {
+            return;
+        }
+
+        // imgui draws its own software cursor, so the OS cursor has to be hidden.
+        let mouse_cursor = if io.mouse_draw_cursor {
+            None
+        } else {
+            ui.mouse_cursor()
+        };
+
+        if !self.os_cursor_visible {
+            self.cursor = (mouse_cursor.unwrap_or(MouseCursor::Arrow), None);
+            self.set_cursor_mode(window, glfw::CursorMode::Hidden);
+            return;
+        }
+
+        match mouse_cursor {
+            Some(mouse_cursor) if self.custom_cursor_active => {
+                self.set_cursor_mode(window, glfw::CursorMode::Normal);
+
+                // The window keeps the custom cursor once it is set.
+                if let Some(custom_cursor) = self.custom_cursor.take() {
+                    window.set_cursor(custom_cursor);
+                }
+                self.cursor = (mouse_cursor, None);
+            }
+            Some(mouse_cursor) => {
+                self.set_cursor_mode(window, glfw::CursorMode::Normal);
+
+                let cursor = match mouse_cursor {
+                    MouseCursor::TextInput => StandardCursor::IBeam,
+                    MouseCursor::ResizeNS => StandardCursor::VResize,
+                    MouseCursor::ResizeEW => StandardCursor::HResize,
+                    MouseCursor::Hand => StandardCursor::Hand,
+                    MouseCursor::Arrow => requested_cursor.unwrap_or(StandardCursor::Arrow),
+                    _ => StandardCursor::Arrow,
+                };
+                if self.cursor.1 != Some(cursor) {
+                    window.set_cursor(glfw::Cursor::standard(cursor));
+                }
+                self.cursor = (mouse_cursor, Some(cursor));
+            }
+            None => {
+                self.cursor = (MouseCursor::Arrow, None);
+                self.set_cursor_mode(window, glfw::CursorMode::Hidden);
+            }
+        }
+    }
+
+    /// Changing the cursor mode every frame makes the cursor flicker on some platforms.
+    fn set_cursor_mode<W: CursorWindow>(&mut self, window: &mut W, mode: glfw::CursorMode) {
+        if self.cursor_mode != Some(mode) {
+            window.set_cursor_mode(mode);
+            self.cursor_mode = Some(mode);
+        }
+    }
+
+    /// Shared by `draw` and `prepare_render`, whether the frame can be rendered.
+    fn begin_render<W: CursorWindow>(&mut self, ui: &Ui, window: &mut W) -> bool {
+        self.end_frame();
+
+        // Minimized windows report a zero size, which imgui can't render.
+        if self.iconified || !Self::is_renderable(ui.io()) {
+            return false;
+        }
+
+        self.typing = ui.io().want_text_input;
+        self.update_cursors(ui, window);
+        Self::ensure_draw_list();
+        true
+    }
+
+    /// `DrawData::draw_lists` builds a slice from imgui's list pointer, which is null
+    /// when nothing was drawn, e.g. in a frame without windows. That panics in debug builds,
+    /// and the renderer reads the lists right after rendering. Whether a frame draws
+    /// anything is only known after rendering it, so the background list always gets a
+    /// transparent, zero sized rectangle when it is empty.
+    fn ensure_draw_list() {
+        unsafe {
+            let draw_list = imgui::sys::igGetBackgroundDrawList();
+            if (*draw_list).VtxBuffer.Size == 0 {
+                let origin = imgui::sys::ImVec2 { x: 0., y: 0. };
+                imgui::sys::ImDrawList_PrimReserve(draw_list, 6, 4);
+                imgui::sys::ImDrawList_PrimRect(draw_list, origin, origin, 0);
+            }
+        }
+    }
+
+    /// Rendering with a degenerate display size panics inside imgui's draw data.
+    fn is_renderable(io: &imgui::Io) -> bool {
+        let [width, height] = io.display_size;
+        let [scale_x, scale_y] = io.display_framebuffer_scale;
+        width.is_finite()
+            && height.is_finite()
+            && width > 0.
+            && height > 0.
+            && scale_x.is_finite()
+            && scale_y.is_finite()
+            && scale_x != 0.
+            && scale_y != 0.
+    }
+
+    #[cfg(feature = "opengl")]
+    fn rebuild_dpi_fonts(&mut self, imgui: &mut Context, window: &mut Window) {
+        let scale = window.get_content_scale().0;
+        if !scale.is_finite() || scale <= 0. {
+            return;
+        }
+
+        match self.auto_dpi_fonts.as_ref() {
+            Some((font_data, base_size)) => {
+                let mut fonts = imgui.fonts();
+                fonts.clear();
+                fonts.add_font(&[FontSource::TtfData {
+                    data: font_data,
+                    size_pixels: base_size * scale,
+                    config: None,
+                }]);
+            }
+            None => return,
+        }
+        self.reload_fonts(imgui, window);
+        self.apply_dpi_scale(imgui, scale);
+    }
+
+    /// The fonts are built at the content scale, but imgui works in window coordinates,
+    /// which the framebuffer scale already enlarges on e.g. macOS. Text and style only get
+    /// the part of the content scale the framebuffer scale doesn't cover.
+    #[cfg(feature = "opengl")]
+    fn apply_dpi_scale(&mut self, imgui: &mut Context, content_scale: f32) {
+        let framebuffer_scale = imgui.io().display_framebuffer_scale[0];
+        let framebuffer_scale = if framebuffer_scale.is_finite() && framebuffer_scale > 0. {
+            framebuffer_scale
+        } else {
+            1.
+        };
+        self.font_scale = 1. / framebuffer_scale;
+        imgui.io_mut().font_global_scale = self.ui_scale * self.font_scale;
+        self.dpi_style_scale = content_scale / framebuffer_scale;
+        self.rescale_style(imgui);
+    }
+
+    /// Scale the style from an unscaled copy. Scaling it relative to the previous scale
+    /// drifts, imgui rounds most sizes down. The current colors are kept.
+    fn rescale_style(&mut self, imgui: &mut Context) {
+        #[cfg(feature = "opengl")]
+        let scale = self.ui_scale * self.auto_style_scale * self.dpi_style_scale;
+        #[cfg(not(feature = "opengl"))]
+        let scale = self.ui_scale * self.auto_style_scale;
+
+        let style = imgui.style_mut();
+        let colors = style.colors;
+        *style = *self.base_style.get_or_insert(*style);
+        style.colors = colors;
+        style.scale_all_sizes(scale);
+    }
+
+    fn update_auto_style_scale(&mut self, imgui: &mut Context) {
+        let scale = match self.auto_style_base_size {
+            Some([base_width, base_height]) => {
+                let [width, height] = imgui.io().display_size;
+                (width / base_width).min(height / base_height)
+            }
+            None => 1.,
+        };
+
+        if scale.is_finite() && scale > 0. && scale != self.auto_style_scale {
+            self.auto_style_scale = scale;
+            self.rescale_style(imgui);
+        }
+    }
+
+    fn convert_style_colors(imgui: &mut Context, convert: fn(f32) -> f32) {
+        for color in imgui.style_mut().colors.iter_mut() {
+            for channel in color.iter_mut().take(3) {
+                *channel = convert(*channel);
+            }
+        }
+    }
+
+    fn is_captured(io: &imgui::Io, event: &WindowEvent) -> bool {
+        match *event {
+            WindowEvent::MouseButton(..) | WindowEvent::CursorPos(..) | WindowEvent::Scroll(..) => {
+                io.want_capture_mouse
+            }
+            WindowEvent::Key(..) | WindowEvent::Char(..) | WindowEvent::CharModifiers(..) => {
+                io.want_capture_keyboard
+            }
+            _ => false,
+        }
+    }
+
+    fn to_imgui_mouse_pos(&self, x: f64, y: f64) -> [f32; 2] {
+        [
+            x as f32 * self.mouse_scale[0],
+            y as f32 * self.mouse_scale[1],
+        ]
+    }
+
+    fn frame_delta(last_frame: Option<Instant>, now: Instant, max_delta: f32) -> f32 {
+        // The time between construction and the first frame (loading fonts, setting up GL, ...)
+        // is not frame time. imgui needs a positive delta, so the first frame reports a
+        // negligible one.
+        let delta_s = match last_frame {
+            Some(last_frame) => {
+                let delta = now - last_frame;
+                delta.as_secs() as f32 + delta.subsec_nanos() as f32 / 1_000_000_000.0
+            }
+            None => FIRST_FRAME_DELTA,
+        };
+        // Avoid animations jumping after the app was paused or backgrounded.
+        delta_s.min(max_delta)
+    }
+
+    /// Split the pending scroll distance into the part passed to imgui this frame and the
+    /// rest.
+    fn smooth_scroll_step(pending: f32, smoothing: f32) -> (f32, f32) {
+        // Hand out what's left at once when it's too small to notice.
+        let step = if pending.abs() < 0.001 {
+            pending
+        } else {
+            pending * (1. - smoothing)
+        };
+        (step, pending - step)
+    }
+
+    fn sanitize_scroll(value: f64) -> f32 {
+        if value.is_finite() {
+            (value as f32).clamp(-MAX_SCROLL, MAX_SCROLL)
+        } else {
+            0.
+        }
+    }
+
+    /// Letter keys are the only ones translated by `layout_key`.
+    fn is_letter_key(key: Key) -> bool {
+        (Key::A as i32..=Key::Z as i32).contains(&(key as i32))
+    }
+
+    /// The letter key the user's keyboard layout produces at the position of `key`.
+    fn layout_key(key: Key, scancode: glfw::Scancode) -> Key {
+        let name = match glfw::get_key_name(Some(key), Some(scancode)) {
+            Some(name) => name,
+            None => return key,
+        };
+        let mut chars = name.chars();
+        let letter = match (chars.next(), chars.next()) {
+            (Some(letter), None) => letter.to_ascii_uppercase(),
+            _ => return key,
+        };
+        match letter {
+            'A' => Key::A,
+            'B' => Key::B,
+            'C' => Key::C,
+            'D' => Key::D,
+            'E' => Key::E,
+            'F' => Key::F,
+            'G' => Key::G,
+            'H' => Key::H,
+            'I' => Key::I,
+            'J' => Key::J,
+            'K' => Key::K,
+            'L' => Key::L,
+            'M' => Key::M,
+            'N' => Key::N,
+            'O' => Key::O,
+            'P' => Key::P,
+            'Q' => Key::Q,
+            'R' => Key::R,
+            'S' => Key::S,
+            'T' => Key::T,
+            'U' => Key::U,
+            'V' => Key::V,
+            'W' => Key::W,
+            'X' => Key::X,
+            'Y' => Key::Y,
+            'Z' => Key::Z,
+            _ => key,
+        }
+    }
+
+    fn key_modifier(key: Key) -> Option<Modifiers> {
+        match key {
+            Key::LeftShift | Key::RightShift => Some(Modifiers::Shift),
+            Key::LeftControl | Key::RightControl => Some(Modifiers::Control),
+            Key::LeftAlt | Key::RightAlt => Some(Modifiers::Alt),
+            Key::LeftSuper | Key::RightSuper => Some(Modifiers::Super),
+            _ => None,
+        }
+    }
+
+    fn set_mod(imgui: &mut Context, modifier: Modifiers) {
+        imgui.io_mut().key_ctrl = modifier.intersects(Modifiers::Control);
+        imgui.io_mut().key_alt = modifier.intersects(Modifiers::Alt);
+        imgui.io_mut().key_shift = modifier.intersects(Modifiers::Shift);
+        imgui.io_mut().key_super = modifier.intersects(Modifiers::Super);
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imgui_glfw(imgui: &mut Context) -> ImguiGLFW {
+        let mut imgui_glfw = ImguiGLFWBuilder::new()
+            .with_headless(true)
+            .with_clipboard(false)
+            .build_for(imgui, None);
+        imgui_glfw.set_window_size(800, 600);
+        imgui_glfw.set_framebuffer_size(800, 600);
+        imgui_glfw
+    }
+
+    #[test]
+    fn disabling_input_releases_held_input() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        imgui_glfw.inject_mouse_button(&mut imgui, MouseButton::Button1, Action::Press);
+        imgui_glfw.inject_key(&mut imgui, Key::A, Action::Press, Modifiers::Control);
+        imgui_glfw.set_input_enabled(&mut imgui, false);
+        // The release is dropped while input is disabled.
+        let release =
+            WindowEvent::MouseButton(MouseButton::Button1, Action::Release, Modifiers::empty());
+        imgui_glfw.handle_event(&mut imgui, &release);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+
+        let io = imgui.io();
+        assert!(!io.mouse_down[0]);
+        assert!(!io.keys_down[Key::A as usize]);
+        assert!(!io.key_ctrl);
+
+        imgui_glfw.set_input_enabled(&mut imgui, true);
+        let press =
+            WindowEvent::MouseButton(MouseButton::Button1, Action::Press, Modifiers::empty());
+        imgui_glfw.handle_event(&mut imgui, &press);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        assert!(imgui.io().mouse_down[0]);
+    }
+
+    #[test]
+    fn extra_mouse_buttons_are_ignored() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        let press =
+            WindowEvent::MouseButton(MouseButton::Button6, Action::Press, Modifiers::empty());
+        imgui_glfw.handle_event(&mut imgui, &press);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        assert!(!imgui.io().mouse_down[0]);
+    }
+
+    #[test]
+    fn quick_clicks_are_not_lost() {
+        let mut imgui = Context::create();
+        imgui.fonts().build_rgba32_texture();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let click = |imgui_glfw: &mut ImguiGLFW, imgui: &mut Context| {
+            for &action in &[Action::Press, Action::Release] {
+                let event =
+                    WindowEvent::MouseButton(MouseButton::Button1, action, Modifiers::empty());
+                imgui_glfw.handle_event(imgui, &event);
+            }
+        };
+
+        // imgui counts the first click within the double click time after startup as a
+        // double click, let some time pass first.
+        for _ in 0..4 {
+            imgui_glfw.last_frame = Instant::now().checked_sub(Duration::from_secs(1));
+            imgui_glfw.begin_frame_detached(&mut imgui);
+            drop(imgui.frame());
+        }
+
+        // Pressed and released between two frames.
+        click(&mut imgui_glfw, &mut imgui);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        assert!(imgui.io().mouse_down[0]);
+        let ui = imgui.frame();
+        assert!(ui.is_mouse_clicked(imgui::MouseButton::Left));
+        assert!(!ui.is_mouse_double_clicked(imgui::MouseButton::Left));
+        drop(ui);
+
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        assert!(!imgui.io().mouse_down[0]);
+        drop(imgui.frame());
+
+        click(&mut imgui_glfw, &mut imgui);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        let ui = imgui.frame();
+        assert!(ui.is_mouse_double_clicked(imgui::MouseButton::Left));
+    }
+
+    #[test]
+    fn cursor_is_scaled_to_framebuffer_pixels() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        imgui_glfw.use_framebuffer_size_for_display(true);
+        imgui_glfw.set_window_size(400, 300);
+        imgui_glfw.set_framebuffer_size(800, 600);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+
+        imgui_glfw.handle_event(&mut imgui, &WindowEvent::CursorPos(100., 100.));
+        assert_eq!(imgui.io().display_size, [800., 600.]);
+        assert_eq!(imgui.io().mouse_pos, [200., 200.]);
+    }
+
+    #[test]
+    fn handle_events_reports_captured_events() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let events = [WindowEvent::Focus(true), WindowEvent::Scroll(0., 1.)];
+
+        assert!(!imgui_glfw.handle_events(&mut imgui, events.iter()));
+        imgui.io_mut().want_capture_mouse = true;
+        assert!(imgui_glfw.handle_events(&mut imgui, events.iter()));
+        imgui_glfw.set_input_enabled(&mut imgui, false);
+        assert!(!imgui_glfw.handle_events(&mut imgui, events.iter()));
+    }
+
+    #[test]
+    fn disabled_clipboard_keeps_imguis_own() {
+        let mut imgui = Context::create();
+        imgui.io_mut().display_size = [800., 600.];
+        imgui.fonts().build_rgba32_texture();
+        ImguiGLFWBuilder::new()
+            .with_headless(true)
+            .with_clipboard(false)
+            .build_for(&mut imgui, None);
+
+        // imgui's default clipboard only lives in memory, glfw's would go through the window.
+        let ui = imgui.frame();
+        ui.set_clipboard_text(imgui::im_str!("imgui-glfw-rs"));
+        let text = ui.clipboard_text();
+        assert_eq!(
+            text.as_ref().map(|text| text.to_str()),
+            Some("imgui-glfw-rs")
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_ignored() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        imgui_glfw.inject_key(&mut imgui, Key::Unknown, Action::Press, Modifiers::Shift);
+        assert!(imgui.io().key_shift);
+        assert!(imgui.io().keys_down.iter().all(|down| !down));
+    }
+
+    #[test]
+    fn releasing_shift_clears_the_modifier() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        let press = WindowEvent::Key(Key::LeftShift, 0, Action::Press, Modifiers::Shift);
+        imgui_glfw.handle_event(&mut imgui, &press);
+        assert!(imgui.io().key_shift);
+        // Some platforms still report Shift in the release of the key itself.
+        let release = WindowEvent::Key(Key::LeftShift, 0, Action::Release, Modifiers::Shift);
+        imgui_glfw.handle_event(&mut imgui, &release);
+        assert!(!imgui.io().key_shift);
+    }
+
+    #[test]
+    fn modifier_combos_are_held_together() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        let ctrl = WindowEvent::Key(Key::LeftControl, 0, Action::Press, Modifiers::empty());
+        imgui_glfw.handle_event(&mut imgui, &ctrl);
+        let shift = WindowEvent::Key(Key::LeftShift, 0, Action::Press, Modifiers::Control);
+        imgui_glfw.handle_event(&mut imgui, &shift);
+        assert!(imgui.io().key_ctrl);
+        assert!(imgui.io().key_shift);
+    }
+
+    #[test]
+    fn key_repeat_keeps_the_key_down() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let mut key = |imgui: &mut Context, action| {
+            let event = WindowEvent::Key(Key::Backspace, 0, action, Modifiers::empty());
+            imgui_glfw.handle_event(imgui, &event);
+            imgui.io().keys_down[Key::Backspace as usize]
+        };
+
+        assert!(key(&mut imgui, Action::Press));
+        assert!(key(&mut imgui, Action::Repeat));
+        assert!(!key(&mut imgui, Action::Release));
+        assert!(!key(&mut imgui, Action::Repeat));
+    }
+
+    #[test]
+    fn long_pauses_are_limited_to_the_max_delta() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        for &max_delta in &[f32::NAN, f32::INFINITY, 0., -1.] {
+            imgui_glfw.set_max_delta(max_delta);
+        }
+        assert_eq!(imgui_glfw.max_delta, 0.1);
+
+        let last_frame = Instant::now();
+        let now = last_frame + Duration::from_secs(365 * 24 * 60 * 60);
+        let delta = ImguiGLFW::frame_delta(Some(last_frame), now, imgui_glfw.max_delta);
+        assert_eq!(delta, 0.1);
+        assert_eq!(
+            ImguiGLFW::frame_delta(None, now, imgui_glfw.max_delta),
+            FIRST_FRAME_DELTA
+        );
+    }
+
+    #[test]
+    fn scroll_is_sanitized() {
+        assert_eq!(ImguiGLFW::sanitize_scroll(f64::NAN), 0.);
+        assert_eq!(ImguiGLFW::sanitize_scroll(f64::INFINITY), 0.);
+        assert_eq!(ImguiGLFW::sanitize_scroll(1e300), MAX_SCROLL);
+        assert_eq!(ImguiGLFW::sanitize_scroll(-1e300), -MAX_SCROLL);
+        assert_eq!(ImguiGLFW::sanitize_scroll(-1.5), -1.5);
+    }
+
+    #[test]
+    fn scroll_events_within_a_frame_add_up() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+
+        for &(x, y) in &[(0.5, 1.), (0.25, 2.), (0., -0.5)] {
+            imgui_glfw.handle_event(&mut imgui, &WindowEvent::Scroll(x, y));
+        }
+        assert_eq!(imgui.io().mouse_wheel_h, 0.75);
+        assert_eq!(imgui.io().mouse_wheel, 2.5);
+    }
+
+    #[test]
+    fn smoothed_scroll_adds_up_to_the_input() {
+        for &smoothing in &[0.5, 0.9, 0.99] {
+            let (mut pending, mut total) = (-37.5, 0.);
+            while pending != 0. {
+                let (step, rest) = ImguiGLFW::smooth_scroll_step(pending, smoothing);
+                total += step;
+                pending = rest;
+            }
+            assert!(
+                (total - -37.5f32).abs() < 1e-3,
+                "{} for {}",
+                total,
+                smoothing
+            );
+        }
+    }
+
+    #[test]
+    fn shift_scroll_is_horizontal_only_when_enabled() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let scroll = WindowEvent::Scroll(0., 1.);
+        let shift = |action| WindowEvent::Key(Key::LeftShift, 0, action, Modifiers::empty());
+        let wheel = |imgui: &mut Context| {
+            let io = imgui.io_mut();
+            let wheel = [io.mouse_wheel_h, io.mouse_wheel];
+            io.mouse_wheel_h = 0.;
+            io.mouse_wheel = 0.;
+            wheel
+        };
+
+        imgui_glfw.handle_event(&mut imgui, &shift(Action::Press));
+        imgui_glfw.handle_event(&mut imgui, &scroll);
+        assert_eq!(wheel(&mut imgui), [0., 1.]);
+
+        imgui_glfw.set_shift_scroll_horizontal(true);
+        imgui_glfw.handle_event(&mut imgui, &scroll);
+        assert_eq!(wheel(&mut imgui), [1., 0.]);
+
+        imgui_glfw.handle_event(&mut imgui, &shift(Action::Release));
+        imgui_glfw.handle_event(&mut imgui, &scroll);
+        assert_eq!(wheel(&mut imgui), [0., 1.]);
+    }
+
+    #[test]
+    fn degenerate_display_is_not_renderable() {
+        let mut imgui = Context::create();
+        assert!(!ImguiGLFW::is_renderable(imgui.io()));
+
+        imgui.io_mut().display_size = [800., 600.];
+        assert!(ImguiGLFW::is_renderable(imgui.io()));
+
+        imgui.io_mut().display_framebuffer_scale = [0., 0.];
+        assert!(!ImguiGLFW::is_renderable(imgui.io()));
+
+        imgui.io_mut().display_framebuffer_scale = [1., 1.];
+        imgui.io_mut().display_size = [f32::NAN, 600.];
+        assert!(!ImguiGLFW::is_renderable(imgui.io()));
+    }
+
+    #[test]
+    fn ui_scale_does_not_drift() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let padding = imgui.style().window_padding;
+
+        for scale in &[1.3, 0.7, 2.1, 1.] {
+            imgui_glfw.set_ui_scale(&mut imgui, *scale);
+        }
+        assert_eq!(imgui.style().window_padding, padding);
+    }
+
+    #[cfg(feature = "opengl")]
+    #[test]
+    fn dpi_scale_leaves_out_the_framebuffer_scale() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let style = *imgui.style();
+
+        // Retina: the framebuffer scale already covers the content scale.
+        imgui.io_mut().display_framebuffer_scale = [2., 2.];
+        imgui_glfw.apply_dpi_scale(&mut imgui, 2.);
+        assert_eq!(imgui.io().font_global_scale, 0.5);
+        assert_eq!(imgui.style().window_padding, style.window_padding);
+
+        // Windows: the framebuffer isn't scaled, the style takes the whole content scale.
+        imgui.io_mut().display_framebuffer_scale = [1., 1.];
+        imgui_glfw.apply_dpi_scale(&mut imgui, 1.5);
+        let mut scaled = style;
+        scaled.scale_all_sizes(1.5);
+        assert_eq!(imgui.io().font_global_scale, 1.);
+        assert_eq!(imgui.style().window_padding, scaled.window_padding);
+    }
+
+    #[test]
+    fn auto_style_scale_does_not_drift() {
+        let mut imgui = Context::create();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let padding = imgui.style().window_padding;
+        imgui_glfw.set_auto_style_scale(Some([800., 600.]));
+
+        for &(width, height) in &[(1043, 811), (517, 389), (1920, 1080), (800, 600)] {
+            imgui_glfw.set_window_size(width, height);
+            imgui_glfw.begin_frame_detached(&mut imgui);
+        }
+        assert_eq!(imgui.style().window_padding, padding);
+    }
+
+    #[test]
+    fn frame_without_windows_has_a_draw_list() {
+        let mut imgui = Context::create();
+        imgui.io_mut().display_size = [800., 600.];
+        imgui.fonts().build_rgba32_texture();
+
+        let ui = imgui.frame();
+        ImguiGLFW::ensure_draw_list();
+        let draw_lists: Vec<_> = ui.render().draw_lists().collect();
+        assert_eq!(draw_lists.len(), 1);
+        assert!(draw_lists[0]
+            .vtx_buffer()
+            .iter()
+            .all(|vertex| vertex.col == [0; 4]));
+    }
+
+    #[derive(Default)]
+    struct CursorCalls {
+        modes: Vec<glfw::CursorMode>,
+        cursors: usize,
+    }
+
+    impl CursorWindow for CursorCalls {
+        fn set_cursor_mode(&mut self, mode: glfw::CursorMode) {
+            self.modes.push(mode);
+        }
+
+        fn set_cursor(&mut self, _cursor: glfw::Cursor) {
+            self.cursors += 1;
+        }
+    }
+
+    #[test]
+    fn steady_frames_dont_set_the_cursor_again() {
+        let mut imgui = Context::create();
+        imgui.fonts().build_rgba32_texture();
+        let mut imgui_glfw = ImguiGLFWBuilder::new()
+            .with_clipboard(false)
+            .build_for(&mut imgui, None);
+        imgui_glfw.set_window_size(800, 600);
+        imgui_glfw.set_framebuffer_size(800, 600);
+        let mut window = CursorCalls::default();
+        let mut frames = |imgui_glfw: &mut ImguiGLFW, window: &mut CursorCalls| {
+            for _ in 0..3 {
+                imgui_glfw.begin_frame_detached(&mut imgui);
+                let ui = imgui.frame();
+                imgui_glfw.update_cursors(&ui, window);
+            }
+        };
+
+        frames(&mut imgui_glfw, &mut window);
+        assert_eq!(window.modes, [glfw::CursorMode::Normal]);
+        assert_eq!(window.cursors, 1);
+
+        imgui_glfw.set_os_cursor_visible(false);
+        frames(&mut imgui_glfw, &mut window);
+        assert_eq!(
+            window.modes,
+            [glfw::CursorMode::Normal, glfw::CursorMode::Hidden]
+        );
+        assert_eq!(window.cursors, 1);
+    }
+
+    #[test]
+    fn typing_follows_the_focused_text_input() {
+        let mut imgui = Context::create();
+        imgui.fonts().build_rgba32_texture();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let mut window = CursorCalls::default();
+        let mut text = imgui::ImString::with_capacity(16);
+        let mut frames = |imgui_glfw: &mut ImguiGLFW, text_input: bool| {
+            for _ in 0..4 {
+                imgui_glfw.begin_frame_detached(&mut imgui);
+                let ui = imgui.frame();
+                ui.window(imgui::im_str!("Window")).build(|| {
+                    if text_input {
+                        ui.set_keyboard_focus_here(imgui::FocusedWidget::Next);
+                        ui.input_text(imgui::im_str!("Text"), &mut text).build();
+                    }
+                });
+                imgui_glfw.prepare_render_for(ui, &mut window);
+            }
+        };
+
+        frames(&mut imgui_glfw, true);
+        assert!(imgui_glfw.is_typing());
+        frames(&mut imgui_glfw, false);
+        assert!(!imgui_glfw.is_typing());
+    }
+
+    #[cfg(feature = "opengl")]
+    #[test]
+    fn disabled_rendering_skips_the_draw_but_not_input() {
+        let mut imgui = Context::create();
+        imgui.fonts().build_rgba32_texture();
+        let mut imgui_glfw = imgui_glfw(&mut imgui);
+        let mut window = CursorCalls::default();
+        let post_renders = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = post_renders.clone();
+        imgui_glfw.set_post_render(Some(Box::new(move || counter.set(counter.get() + 1))));
+
+        imgui_glfw.set_rendering_enabled(false);
+        let press =
+            WindowEvent::MouseButton(MouseButton::Button1, Action::Press, Modifiers::empty());
+        imgui_glfw.handle_event(&mut imgui, &press);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        assert!(imgui.io().mouse_down[0]);
+        imgui_glfw.draw_for(imgui.frame(), &mut window);
+        assert_eq!(post_renders.get(), 0);
+
+        imgui_glfw.set_rendering_enabled(true);
+        imgui_glfw.begin_frame_detached(&mut imgui);
+        imgui_glfw.draw_for(imgui.frame(), &mut window);
+        assert_eq!(post_renders.get(), 1);
     }
 }
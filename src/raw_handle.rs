@@ -0,0 +1,121 @@
+//! `raw-window-handle` interop for the glfw window.
+//!
+//! Supported platforms:
+//! - Linux/BSD with X11 (default)
+//! - Linux/BSD with Wayland (enable the `wayland` feature, GLFW must be built for Wayland)
+//! - Windows (Win32)
+//! - macOS (Cocoa)
+
+use glfw::ffi::GLFWwindow;
+use glfw::Context as _;
+use glfw::Window;
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
+use std::os::raw::c_void;
+
+#[cfg(all(unix, not(target_os = "macos"), not(feature = "wayland")))]
+extern "C" {
+    fn glfwGetX11Display() -> *mut c_void;
+    fn glfwGetX11Window(window: *mut GLFWwindow) -> std::os::raw::c_ulong;
+}
+
+#[cfg(all(unix, not(target_os = "macos"), feature = "wayland"))]
+extern "C" {
+    fn glfwGetWaylandDisplay() -> *mut c_void;
+    fn glfwGetWaylandWindow(window: *mut GLFWwindow) -> *mut c_void;
+}
+
+#[cfg(target_os = "windows")]
+extern "C" {
+    fn glfwGetWin32Window(window: *mut GLFWwindow) -> *mut c_void;
+}
+
+#[cfg(target_os = "windows")]
+extern "system" {
+    fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn glfwGetCocoaWindow(window: *mut GLFWwindow) -> *mut c_void;
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "objc")]
+extern "C" {
+    fn sel_registerName(name: *const std::os::raw::c_char) -> *mut c_void;
+    fn objc_msgSend();
+}
+
+/// Get the raw window and display handles of a glfw window.
+pub fn raw_handles(window: &Window) -> (RawWindowHandle, RawDisplayHandle) {
+    platform_handles(window.window_ptr())
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(feature = "wayland")))]
+fn platform_handles(window_ptr: *mut GLFWwindow) -> (RawWindowHandle, RawDisplayHandle) {
+    use raw_window_handle::{XlibDisplayHandle, XlibWindowHandle};
+
+    let mut window_handle = XlibWindowHandle::empty();
+    window_handle.window = unsafe { glfwGetX11Window(window_ptr) };
+    let mut display_handle = XlibDisplayHandle::empty();
+    display_handle.display = unsafe { glfwGetX11Display() };
+
+    (
+        RawWindowHandle::Xlib(window_handle),
+        RawDisplayHandle::Xlib(display_handle),
+    )
+}
+
+#[cfg(all(unix, not(target_os = "macos"), feature = "wayland"))]
+fn platform_handles(window_ptr: *mut GLFWwindow) -> (RawWindowHandle, RawDisplayHandle) {
+    use raw_window_handle::{WaylandDisplayHandle, WaylandWindowHandle};
+
+    let mut window_handle = WaylandWindowHandle::empty();
+    window_handle.surface = unsafe { glfwGetWaylandWindow(window_ptr) };
+    let mut display_handle = WaylandDisplayHandle::empty();
+    display_handle.display = unsafe { glfwGetWaylandDisplay() };
+
+    (
+        RawWindowHandle::Wayland(window_handle),
+        RawDisplayHandle::Wayland(display_handle),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn platform_handles(window_ptr: *mut GLFWwindow) -> (RawWindowHandle, RawDisplayHandle) {
+    use raw_window_handle::{Win32WindowHandle, WindowsDisplayHandle};
+
+    let mut window_handle = Win32WindowHandle::empty();
+    window_handle.hwnd = unsafe { glfwGetWin32Window(window_ptr) };
+    window_handle.hinstance = unsafe { GetModuleHandleW(std::ptr::null()) };
+
+    (
+        RawWindowHandle::Win32(window_handle),
+        RawDisplayHandle::Windows(WindowsDisplayHandle::empty()),
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn platform_handles(window_ptr: *mut GLFWwindow) -> (RawWindowHandle, RawDisplayHandle) {
+    use raw_window_handle::{AppKitDisplayHandle, AppKitWindowHandle};
+
+    let mut window_handle = AppKitWindowHandle::empty();
+    window_handle.ns_window = unsafe { glfwGetCocoaWindow(window_ptr) };
+    if !window_handle.ns_window.is_null() {
+        // `[ns_window contentView]`, objc_msgSend has to be called through the exact
+        // signature of the method.
+        window_handle.ns_view = unsafe {
+            let send: unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void =
+                std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            send(
+                window_handle.ns_window,
+                sel_registerName(b"contentView\0".as_ptr() as *const _),
+            )
+        };
+    }
+
+    (
+        RawWindowHandle::AppKit(window_handle),
+        RawDisplayHandle::AppKit(AppKitDisplayHandle::empty()),
+    )
+}